@@ -0,0 +1,181 @@
+//! Utility module defining methods for parsing bit-level binary formats.
+
+use std::marker::PhantomData;
+
+use crate::{span::Span, Error, Expected, Input, Parser, Source, Stream};
+
+/// A byte slice read as a sequence of bits, most significant bit first.
+///
+/// Positions in an `Input<Bits>`, and in the errors produced while parsing one, are counted in
+/// bits rather than bytes. Slicing a `Bits` produces another `Bits`, which can be parsed on its
+/// own.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Bits<'a> {
+    bytes: &'a [u8],
+    /// The bit offset into the bytes at which these bits start.
+    start: usize,
+    /// The number of bits.
+    len: usize,
+}
+
+impl<'a> Bits<'a> {
+    /// Read the given bytes as bits.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Bits {
+            bytes,
+            start: 0,
+            len: bytes.len() * 8,
+        }
+    }
+
+    /// The number of bits.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether there are no bits.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Get the bit at the given position, or `None` if the position is out of bounds.
+    pub fn get(&self, pos: usize) -> Option<bool> {
+        if pos >= self.len {
+            return None;
+        }
+        let pos = self.start + pos;
+        let byte = self.bytes.get(pos / 8)?;
+        Some(byte & (0x80 >> (pos % 8)) != 0)
+    }
+}
+
+impl Source for Bits<'_> {
+    fn found(&self, pos: usize) -> Option<String> {
+        self.get(pos)
+            .map(|bit| if bit { "1" } else { "0" }.to_string())
+    }
+
+    fn rest(&self, pos: usize) -> Option<String> {
+        let rest: String = (pos..self.len())
+            .filter_map(|pos| self.found(pos))
+            .collect();
+        Some(rest).filter(|rest| !rest.is_empty())
+    }
+}
+
+impl<'a> Stream for Bits<'a> {
+    type Item = bool;
+    type Slice = Bits<'a>;
+    type Span = Span;
+
+    fn next(&self, pos: usize) -> Option<(bool, usize)> {
+        self.get(pos).map(|bit| (bit, pos + 1))
+    }
+
+    fn slice(&self, start: usize, end: usize) -> Bits<'a> {
+        let end = end.min(self.len);
+        let start = start.min(end);
+        Bits {
+            bytes: self.bytes,
+            start: self.start + start,
+            len: end - start,
+        }
+    }
+
+    fn span(&self, start: usize, end: usize) -> Span {
+        Span::new(start, end)
+    }
+}
+
+/// Get the next bit of the input, or an error at the end of the input.
+fn next_bit(input: &mut Input<'_, Bits<'_>>) -> Result<bool, Error> {
+    match input.source.get(input.pos) {
+        Some(bit) => {
+            input.pos += 1;
+            Ok(bit)
+        }
+        None => Err(input.error([Expected::Description("a bit")])),
+    }
+}
+
+/// See [`bit`].
+#[derive(Clone, Copy)]
+struct Bit;
+
+impl<'a> Parser<'a, Bits<'a>, bool> for Bit {
+    fn parse(&self, input: &mut Input<'a, Bits<'a>>) -> Result<bool, Error> {
+        next_bit(input)
+    }
+}
+
+/// A parser that matches a single bit.
+///
+/// # Example
+/// ```
+/// use aocparse::{bits::{bit, Bits}, Input, Parser};
+///
+/// let mut input = Input::from(Bits::new(&[0b1010_0000]));
+/// assert_eq!(bit().repeated().exactly(3).parse(&mut input), Ok(vec![true, false, true]));
+/// ```
+pub fn bit<'a>() -> impl Parser<'a, Bits<'a>, bool> {
+    Bit
+}
+
+/// See [`bits`].
+#[derive(Clone, Copy)]
+pub struct Uint<T> {
+    count: u32,
+    __phantom: PhantomData<fn() -> T>,
+}
+
+macro_rules! uint_impl {
+    ($t:ty) => {
+        impl<'a> Parser<'a, Bits<'a>, $t> for Uint<$t> {
+            fn parse(&self, input: &mut Input<'a, Bits<'a>>) -> Result<$t, Error> {
+                assert!(
+                    self.count <= <$t>::BITS,
+                    "cannot read {} bits into {}",
+                    self.count,
+                    stringify!($t)
+                );
+                input.attempt(|input| {
+                    let mut value: $t = 0;
+                    for _ in 0..self.count {
+                        value = value << 1 | next_bit(input)? as $t;
+                    }
+                    Ok(value)
+                })
+            }
+        }
+    };
+}
+
+uint_impl!(u8);
+uint_impl!(u16);
+uint_impl!(u32);
+uint_impl!(u64);
+uint_impl!(u128);
+uint_impl!(usize);
+
+/// A parser that matches an unsigned integer made up of the next `count` bits, most significant
+/// bit first.
+///
+/// # Panics
+/// Parsing panics if `count` is larger than the number of bits in the output type.
+///
+/// # Example
+/// ```
+/// use aocparse::{bits::{bits, Bits}, Input, Parser};
+///
+/// // the version and type ID of a literal packet from AoC 2021 day 16
+/// let header = bits::<u8>(3).then(bits::<u8>(3));
+/// let mut input = Input::from(Bits::new(&[0xd2, 0xfe, 0x28]));
+/// assert_eq!(header.parse(&mut input), Ok((6, 4)));
+/// assert_eq!(input.pos(), 6);
+/// ```
+pub fn bits<T>(count: u32) -> Uint<T> {
+    Uint {
+        count,
+        __phantom: PhantomData,
+    }
+}
@@ -0,0 +1,55 @@
+//! Defines type-erased parsers, which hide the type of the parser they wrap.
+
+use std::{rc::Rc, sync::Arc};
+
+use crate::{DynParser, Error, Input, Parser};
+
+/// A parser with its type erased, which is cheap to clone.
+///
+/// See [`Parser::boxed`].
+pub struct BoxedParser<'a, I, O> {
+    pub(crate) parser: Rc<dyn DynParser<'a, I, O> + 'a>,
+}
+
+impl<I, O> Clone for BoxedParser<'_, I, O> {
+    fn clone(&self) -> Self {
+        BoxedParser {
+            parser: Rc::clone(&self.parser),
+        }
+    }
+}
+
+impl<'a, I, O> Parser<'a, I, O> for BoxedParser<'a, I, O> {
+    fn parse(&self, input: &mut Input<'a, I>) -> Result<O, Error> {
+        self.parser.parse_dyn(input)
+    }
+
+    fn boxed(self) -> BoxedParser<'a, I, O> {
+        self
+    }
+}
+
+/// A parser with its type erased, which is cheap to clone and can be shared between threads.
+///
+/// See [`Parser::boxed_sync`].
+pub struct SyncBoxedParser<'a, I, O> {
+    pub(crate) parser: Arc<dyn DynParser<'a, I, O> + Send + Sync + 'a>,
+}
+
+impl<I, O> Clone for SyncBoxedParser<'_, I, O> {
+    fn clone(&self) -> Self {
+        SyncBoxedParser {
+            parser: Arc::clone(&self.parser),
+        }
+    }
+}
+
+impl<'a, I, O> Parser<'a, I, O> for SyncBoxedParser<'a, I, O> {
+    fn parse(&self, input: &mut Input<'a, I>) -> Result<O, Error> {
+        self.parser.parse_dyn(input)
+    }
+
+    fn boxed_sync(self) -> SyncBoxedParser<'a, I, O> {
+        self
+    }
+}
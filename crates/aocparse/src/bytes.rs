@@ -0,0 +1,185 @@
+//! Utility module defining methods for parsing byte slices and binary formats.
+
+use std::marker::PhantomData;
+
+use crate::{Error, Expected, Input, Parser};
+
+/// See [`byte`].
+#[derive(Clone, Copy)]
+struct Byte {
+    byte: u8,
+}
+
+impl<'a> Parser<'a, &'a [u8], u8> for Byte {
+    fn parse(&self, input: &mut Input<'a, &'a [u8]>) -> Result<u8, Error> {
+        if input.source.get(input.pos) == Some(&self.byte) {
+            input.pos += 1;
+            Ok(self.byte)
+        } else {
+            Err(input.error([Expected::Byte(self.byte)]))
+        }
+    }
+}
+
+/// A parser that matches the given byte.
+///
+/// # Example
+/// ```
+/// use aocparse::{bytes::byte, Parser};
+///
+/// assert_eq!(byte(b'#').repeated().parse_bytes(b"##."), Ok(vec![b'#', b'#']));
+/// ```
+pub fn byte<'a>(byte: u8) -> impl Parser<'a, &'a [u8], u8> {
+    Byte { byte }
+}
+
+/// See [`bytes`].
+#[derive(Clone, Copy)]
+struct Bytes {
+    token: &'static [u8],
+}
+
+impl<'a> Parser<'a, &'a [u8], ()> for Bytes {
+    fn parse(&self, input: &mut Input<'a, &'a [u8]>) -> Result<(), Error> {
+        if input.source.get(input.pos..input.pos + self.token.len()) == Some(self.token) {
+            input.pos += self.token.len();
+            Ok(())
+        } else {
+            Err(input.error([Expected::Bytes(self.token)]))
+        }
+    }
+}
+
+/// A parser that matches the given sequence of bytes.
+///
+/// This is the byte equivalent of [`just`](crate::text::just).
+///
+/// # Example
+/// ```
+/// use aocparse::{bytes::bytes, Parser};
+///
+/// assert!(bytes(b"GIF89a").parse_bytes(b"GIF89a...").is_ok());
+/// assert!(bytes(b"GIF89a").parse_bytes(b"GIF").is_err());
+/// ```
+pub fn bytes<'a>(token: &'static [u8]) -> impl Parser<'a, &'a [u8], ()> {
+    Bytes { token }
+}
+
+/// Get the next `n` bytes of the input, or an error at the end of the input if there are too
+/// few bytes left.
+fn next_bytes<'a>(input: &mut Input<'a, &'a [u8]>, n: usize) -> Result<&'a [u8], Error> {
+    let end = input.pos.saturating_add(n);
+    match input.source.get(input.pos..end) {
+        Some(bytes) => {
+            input.pos = end;
+            Ok(bytes)
+        }
+        None => Err(Error::new(
+            input.source.len(),
+            [Expected::Description("a byte")],
+            None,
+        )),
+    }
+}
+
+/// See [`take`].
+#[derive(Clone, Copy)]
+struct Take {
+    n: usize,
+}
+
+impl<'a> Parser<'a, &'a [u8], &'a [u8]> for Take {
+    fn parse(&self, input: &mut Input<'a, &'a [u8]>) -> Result<&'a [u8], Error> {
+        next_bytes(input, self.n)
+    }
+}
+
+/// A parser that matches exactly `n` bytes of any value.
+///
+/// # Example
+/// ```
+/// use aocparse::{bytes::take, Parser};
+///
+/// assert_eq!(take(2).parse_bytes(b"abc"), Ok(&b"ab"[..]));
+/// assert!(take(4).parse_bytes(b"abc").is_err());
+/// ```
+pub fn take<'a>(n: usize) -> impl Parser<'a, &'a [u8], &'a [u8]> {
+    Take { n }
+}
+
+/// The byte order of an integer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Endian {
+    Little,
+    Big,
+}
+
+/// See [`le`] and [`be`].
+#[derive(Clone, Copy)]
+pub struct Integer<T> {
+    endian: Endian,
+    __phantom: PhantomData<fn() -> T>,
+}
+
+macro_rules! integer_impl {
+    ($t:ty) => {
+        impl<'a> Parser<'a, &'a [u8], $t> for Integer<$t> {
+            fn parse(&self, input: &mut Input<'a, &'a [u8]>) -> Result<$t, Error> {
+                const SIZE: usize = std::mem::size_of::<$t>();
+                let mut bytes = [0; SIZE];
+                bytes.copy_from_slice(next_bytes(input, SIZE)?);
+                Ok(match self.endian {
+                    Endian::Little => <$t>::from_le_bytes(bytes),
+                    Endian::Big => <$t>::from_be_bytes(bytes),
+                })
+            }
+        }
+    };
+}
+
+integer_impl!(u8);
+integer_impl!(u16);
+integer_impl!(u32);
+integer_impl!(u64);
+integer_impl!(u128);
+integer_impl!(i8);
+integer_impl!(i16);
+integer_impl!(i32);
+integer_impl!(i64);
+integer_impl!(i128);
+
+/// A parser that matches a little-endian integer.
+///
+/// This is generic over all fixed-size integer primitives.
+///
+/// # Example
+/// ```
+/// use aocparse::{bytes::le, Parser};
+///
+/// assert_eq!(le::<u16>().parse_bytes(&[0x34, 0x12]), Ok(0x1234));
+/// assert_eq!(le::<i32>().parse_bytes(&[0xff; 4]), Ok(-1));
+/// ```
+pub fn le<T>() -> Integer<T> {
+    Integer {
+        endian: Endian::Little,
+        __phantom: PhantomData,
+    }
+}
+
+/// A parser that matches a big-endian integer.
+///
+/// This is generic over all fixed-size integer primitives.
+///
+/// # Example
+/// ```
+/// use aocparse::{bytes::be, Parser};
+///
+/// assert_eq!(be::<u16>().parse_bytes(&[0x12, 0x34]), Ok(0x1234));
+/// assert!(be::<u64>().parse_bytes(&[0x12, 0x34]).is_err());
+/// ```
+pub fn be<T>() -> Integer<T> {
+    Integer {
+        endian: Endian::Big,
+        __phantom: PhantomData,
+    }
+}
@@ -1,8 +1,8 @@
 //! Defines combinators for composing parsers.
 
-use std::marker::PhantomData;
+use std::{marker::PhantomData, sync::Arc};
 
-use crate::{Input, Parser};
+use crate::{primitive::Map, Error, Expected, Input, Parser, Source, Stream};
 
 /// A parser that allows for sequencing of two child parsers.
 #[derive(Clone)]
@@ -15,11 +15,14 @@ impl<'a, A, B, I, OA, OB> Parser<'a, I, (OA, OB)> for Then<A, B>
 where
     A: Parser<'a, I, OA>,
     B: Parser<'a, I, OB>,
+    I: Stream,
 {
-    fn parse(&self, input: &mut Input<'a, I>) -> Result<(OA, OB), ()> {
-        let a = self.a.parse(input)?;
-        let b = self.b.parse(input)?;
-        Ok((a, b))
+    fn parse(&self, input: &mut Input<'a, I>) -> Result<(OA, OB), Error> {
+        input.attempt(|input| {
+            let a = self.a.parse(input)?;
+            let b = self.b.parse(input).map_err(|e| input.merge_alt(e))?;
+            Ok((a, b))
+        })
     }
 }
 
@@ -32,73 +35,119 @@ pub struct Maybe<P> {
 impl<'a, I, O, P> Parser<'a, I, Option<O>> for Maybe<P>
 where
     P: Parser<'a, I, O>,
+    I: Stream,
 {
-    fn parse(&self, input: &mut Input<'a, I>) -> Result<Option<O>, ()> {
-        match self.parser.parse(input) {
+    fn parse(&self, input: &mut Input<'a, I>) -> Result<Option<O>, Error> {
+        match input.attempt(|input| self.parser.parse(input)) {
             Ok(o) => Ok(Some(o)),
-            Err(_) => Ok(None),
+            Err(e) if e.committed => Err(e),
+            Err(e) => {
+                input.recover(e);
+                Ok(None)
+            }
         }
     }
 }
 
 /// A parser that allows for repeated parsing using its child parser.
+///
+/// By default, the child parser is applied until it fails. The number of repetitions can be
+/// bounded using [`Repeated::at_least`], [`Repeated::at_most`] and [`Repeated::exactly`].
+///
+/// If the child parser succeeds without consuming any input, it would succeed forever, so the
+/// repetition stops instead.
 pub struct Repeated<P, O> {
     pub(crate) parser: P,
-    pub(crate) __phantom: PhantomData<O>,
+    pub(crate) at_least: usize,
+    pub(crate) at_most: Option<usize>,
+    pub(crate) __phantom: PhantomData<fn() -> O>,
 }
 
-impl<P: Clone, O> Clone for Repeated<P, O> {
-    fn clone(&self) -> Self {
+impl<P, O> Repeated<P, O> {
+    /// Require at least `n` repetitions.
+    pub fn at_least(self, n: usize) -> Self {
         Repeated {
-            parser: self.parser.clone(),
-            __phantom: PhantomData,
+            at_least: n,
+            ..self
         }
     }
-}
 
-impl<'a, I, O, P> Parser<'a, I, Vec<O>> for Repeated<P, O>
-where
-    P: Parser<'a, I, O>,
-{
-    fn parse(&self, input: &mut Input<'a, I>) -> Result<Vec<O>, ()> {
-        let mut result = Vec::new();
-        while let Ok(o) = self.parser.parse(input) {
-            result.push(o);
+    /// Allow at most `n` repetitions.
+    pub fn at_most(self, n: usize) -> Self {
+        Repeated {
+            at_most: Some(n),
+            ..self
         }
-        Ok(result)
     }
-}
 
-/// A parser that allows for repeated parsing using its child parser a fixed number of times.
-pub struct Repeat<P, O> {
-    pub(crate) count: usize,
-    pub(crate) parser: P,
-    pub(crate) __phantom: PhantomData<O>,
+    /// Require exactly `n` repetitions.
+    pub fn exactly(self, n: usize) -> Self {
+        self.at_least(n).at_most(n)
+    }
 }
 
-impl<P, O> Clone for Repeat<P, O>
-where
-    P: Clone,
-{
+impl<P: Clone, O> Clone for Repeated<P, O> {
     fn clone(&self) -> Self {
-        Repeat {
-            count: self.count,
+        Repeated {
             parser: self.parser.clone(),
+            at_least: self.at_least,
+            at_most: self.at_most,
             __phantom: PhantomData,
         }
     }
 }
 
-impl<'a, I, O, P> Parser<'a, I, Vec<O>> for Repeat<P, O>
+impl<'a, I, O, P> Parser<'a, I, Vec<O>> for Repeated<P, O>
 where
     P: Parser<'a, I, O>,
+    I: Stream,
 {
-    fn parse(&self, input: &mut Input<'a, I>) -> Result<Vec<O>, ()> {
-        let mut result = Vec::with_capacity(self.count);
-        for _ in 0..self.count {
-            result.push(self.parser.parse(input)?);
+    fn parse(&self, input: &mut Input<'a, I>) -> Result<Vec<O>, Error> {
+        input.attempt(|input| {
+            let mut result = Vec::new();
+            let mut error = None;
+            while self.at_most.is_none_or(|n| result.len() < n) {
+                let marker = input.save();
+                let step = input.attempt(|input| self.parser.parse(input));
+                let consumed = input.pos != marker.pos;
+                if step.is_ok() && !consumed {
+                    input.rewind(marker);
+                }
+                input.release(marker);
+                match step {
+                    Ok(_) if !consumed => break,
+                    Ok(o) => result.push(o),
+                    Err(e) if e.committed => return Err(e),
+                    Err(e) => {
+                        error = Some(e);
+                        break;
+                    }
+                }
+            }
+            check_at_least(input, result, self.at_least, error)
+        })
+    }
+}
+
+/// Check that a repetition produced enough items, recovering from the error that ended it.
+fn check_at_least<I: Source, O>(
+    input: &mut Input<'_, I>,
+    result: Vec<O>,
+    at_least: usize,
+    error: Option<Error>,
+) -> Result<Vec<O>, Error> {
+    match error {
+        Some(e) if result.len() < at_least => Err(input.merge_alt(e)),
+        None if result.len() < at_least => Err(input.merge_alt(Error::custom(
+            input.pos,
+            format!("expected at least {} repetitions", at_least),
+            input.source.found(input.pos),
+        ))),
+        Some(e) => {
+            input.recover(e);
+            Ok(result)
         }
-        Ok(result)
+        None => Ok(result),
     }
 }
 
@@ -113,29 +162,35 @@ impl<'a, A, B, I, O> Parser<'a, I, O> for Or<A, B>
 where
     A: Parser<'a, I, O> + Sized,
     B: Parser<'a, I, O> + Sized,
+    I: Stream,
 {
-    fn parse(&self, input: &mut Input<'a, I>) -> Result<O, ()> {
-        self.a.parse(input).or_else(|_| self.b.parse(input))
+    fn parse(&self, input: &mut Input<'a, I>) -> Result<O, Error> {
+        match input.attempt(|input| self.a.parse(input)) {
+            Ok(o) => Ok(o),
+            Err(a) if a.committed => Err(a),
+            Err(a) => match input.attempt(|input| self.b.parse(input)) {
+                Ok(o) => {
+                    input.recover(a);
+                    Ok(o)
+                }
+                Err(b) => Err(a.merge(b)),
+            },
+        }
     }
 }
 
 /// See [Parser::foldl].
-#[derive(Copy)]
 pub struct Foldl<P, F, O> {
     pub(crate) parser: P,
-    pub(crate) f: F,
-    pub(crate) __phantom: PhantomData<O>,
+    pub(crate) f: Arc<F>,
+    pub(crate) __phantom: PhantomData<fn() -> O>,
 }
 
-impl<P, F, O> Clone for Foldl<P, F, O>
-where
-    P: Clone,
-    F: Clone,
-{
+impl<P: Clone, F, O> Clone for Foldl<P, F, O> {
     fn clone(&self) -> Self {
         Foldl {
             parser: self.parser.clone(),
-            f: self.f.clone(),
+            f: Arc::clone(&self.f),
             __phantom: PhantomData,
         }
     }
@@ -144,50 +199,500 @@ where
 impl<'a, P, I, O, F> Parser<'a, I, O> for Foldl<P, F, O>
 where
     P: Parser<'a, I, Vec<O>>,
-    F: Fn(O, O) -> O + Clone + Copy,
+    F: Fn(O, O) -> O,
+    I: Stream,
 {
-    fn parse(&self, input: &mut Input<'a, I>) -> Result<O, ()> {
-        self.parser.parse(input).map(|mut v| {
-            // TODO: fails if v is empty
-            let mut result = v.pop().unwrap();
-            for o in v {
-                result = (self.f)(result, o);
+    fn parse(&self, input: &mut Input<'a, I>) -> Result<O, Error> {
+        let start = input.pos;
+        input.attempt(|input| {
+            let mut items = self.parser.parse(input)?.into_iter();
+            match items.next() {
+                Some(first) => Ok(items.fold(first, &*self.f)),
+                None => Err(Error::custom(
+                    start,
+                    "expected at least one item to fold",
+                    input.source.found(start),
+                )),
             }
-            result
         })
     }
 }
 
-/// See [Parser::delimited_by].
-#[derive(Clone)]
-pub struct DelimitedBy<A, D> {
+/// See [Parser::separated_by].
+///
+/// The number of items can be bounded using [`SeparatedBy::at_least`] and
+/// [`SeparatedBy::at_most`], and a leading or trailing separator can be allowed using
+/// [`SeparatedBy::allow_leading`] and [`SeparatedBy::allow_trailing`].
+pub struct SeparatedBy<A, S, OS> {
     pub(crate) item: A,
-    pub(crate) delimiter: D,
+    pub(crate) separator: S,
+    pub(crate) at_least: usize,
+    pub(crate) at_most: Option<usize>,
+    pub(crate) allow_leading: bool,
+    pub(crate) allow_trailing: bool,
+    pub(crate) __phantom: PhantomData<fn() -> OS>,
 }
 
-impl<'a, A, D, I, O> Parser<'a, I, Vec<O>> for DelimitedBy<A, D>
+impl<A, S, OS> SeparatedBy<A, S, OS> {
+    /// Require at least `n` items.
+    pub fn at_least(self, n: usize) -> Self {
+        SeparatedBy {
+            at_least: n,
+            ..self
+        }
+    }
+
+    /// Allow at most `n` items.
+    pub fn at_most(self, n: usize) -> Self {
+        SeparatedBy {
+            at_most: Some(n),
+            ..self
+        }
+    }
+
+    /// Require exactly `n` items.
+    pub fn exactly(self, n: usize) -> Self {
+        self.at_least(n).at_most(n)
+    }
+
+    /// Allow a separator before the first item.
+    pub fn allow_leading(self) -> Self {
+        SeparatedBy {
+            allow_leading: true,
+            ..self
+        }
+    }
+
+    /// Allow a separator after the last item.
+    pub fn allow_trailing(self) -> Self {
+        SeparatedBy {
+            allow_trailing: true,
+            ..self
+        }
+    }
+
+    /// Try to parse an optional separator, recovering if it is not present.
+    fn optional_separator<'a, I>(&self, input: &mut Input<'a, I>) -> Result<(), Error>
+    where
+        S: Parser<'a, I, OS>,
+        I: Stream,
+    {
+        match input.attempt(|input| self.separator.parse(input)) {
+            Err(e) if e.committed => Err(e),
+            Err(e) => {
+                input.recover(e);
+                Ok(())
+            }
+            Ok(_) => Ok(()),
+        }
+    }
+}
+
+impl<A: Clone, S: Clone, OS> Clone for SeparatedBy<A, S, OS> {
+    fn clone(&self) -> Self {
+        SeparatedBy {
+            item: self.item.clone(),
+            separator: self.separator.clone(),
+            at_least: self.at_least,
+            at_most: self.at_most,
+            allow_leading: self.allow_leading,
+            allow_trailing: self.allow_trailing,
+            __phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, A, S, I, O, OS> Parser<'a, I, Vec<O>> for SeparatedBy<A, S, OS>
 where
     A: Parser<'a, I, O>,
-    D: Parser<'a, I, ()>,
+    S: Parser<'a, I, OS>,
+    I: Stream,
 {
-    fn parse(&self, input: &mut Input<'a, I>) -> Result<Vec<O>, ()> {
-        let mut items = self.item.parse(input).map_or(vec![], |v| vec![v]);
-        let mut prev_pos = input.pos;
-        loop {
-            self.delimiter.parse(input)?;
-            match self.item.parse(input) {
-                Ok(item) => {
-                    items.push(item);
-                    prev_pos = input.pos;
+    fn parse(&self, input: &mut Input<'a, I>) -> Result<Vec<O>, Error> {
+        input.attempt(|input| {
+            if self.allow_leading {
+                self.optional_separator(input)?;
+            }
+
+            let mut items = Vec::new();
+            let mut error = None;
+            while self.at_most.is_none_or(|n| items.len() < n) {
+                // rewind to the last successful item if the separator is not followed by an item
+                let marker = input.save();
+                let result = input.attempt(|input| {
+                    if !items.is_empty() {
+                        self.separator.parse(input)?;
+                    }
+                    self.item.parse(input)
+                });
+                let consumed = input.pos != marker.pos;
+                if result.is_ok() && !consumed {
+                    input.rewind(marker);
                 }
-                Err(_) => {
-                    // rewind to last successful parse
-                    input.pos = prev_pos;
-                    break;
+                input.release(marker);
+                match result {
+                    Ok(_) if !consumed => break,
+                    Ok(item) => items.push(item),
+                    Err(e) if e.committed => return Err(e),
+                    Err(e) => {
+                        error = Some(e);
+                        break;
+                    }
                 }
             }
+
+            if self.allow_trailing && !items.is_empty() {
+                self.optional_separator(input)?;
+            }
+            check_at_least(input, items, self.at_least, error)
+        })
+    }
+}
+
+/// See [Parser::cut].
+#[derive(Clone)]
+pub struct Cut<P> {
+    pub(crate) parser: P,
+}
+
+impl<'a, I, O, P> Parser<'a, I, O> for Cut<P>
+where
+    P: Parser<'a, I, O>,
+{
+    fn parse(&self, input: &mut Input<'a, I>) -> Result<O, Error> {
+        self.parser.parse(input).map_err(|mut e| {
+            e.committed = true;
+            e
+        })
+    }
+}
+
+/// See [Parser::rewind].
+#[derive(Clone)]
+pub struct Rewind<P> {
+    pub(crate) parser: P,
+}
+
+impl<'a, I, O, P> Parser<'a, I, O> for Rewind<P>
+where
+    P: Parser<'a, I, O>,
+    I: Stream,
+{
+    fn parse(&self, input: &mut Input<'a, I>) -> Result<O, Error> {
+        let marker = input.save();
+        let result = self.parser.parse(input);
+        input.rewind(marker);
+        input.release(marker);
+        result
+    }
+}
+
+/// See [Parser::and_is].
+pub struct AndIs<A, B, OB> {
+    pub(crate) a: A,
+    pub(crate) b: B,
+    pub(crate) __phantom: PhantomData<fn() -> OB>,
+}
+
+impl<A: Clone, B: Clone, OB> Clone for AndIs<A, B, OB> {
+    fn clone(&self) -> Self {
+        AndIs {
+            a: self.a.clone(),
+            b: self.b.clone(),
+            __phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, A, B, I, O, OB> Parser<'a, I, O> for AndIs<A, B, OB>
+where
+    A: Parser<'a, I, O>,
+    B: Parser<'a, I, OB>,
+    I: Stream,
+{
+    fn parse(&self, input: &mut Input<'a, I>) -> Result<O, Error> {
+        input.attempt(|input| {
+            let start = input.save();
+            let result = self.a.parse(input).and_then(|output| {
+                let end = input.save();
+                input.rewind(start);
+                let result = self.b.parse(input).map(|_| output);
+                input.rewind(end);
+                input.release(end);
+                result
+            });
+            input.release(start);
+            result
+        })
+    }
+}
+
+/// See [Parser::not].
+pub struct Not<P, O> {
+    pub(crate) parser: P,
+    pub(crate) __phantom: PhantomData<fn() -> O>,
+}
+
+impl<P: Clone, O> Clone for Not<P, O> {
+    fn clone(&self) -> Self {
+        Not {
+            parser: self.parser.clone(),
+            __phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, I, O, P> Parser<'a, I, ()> for Not<P, O>
+where
+    P: Parser<'a, I, O>,
+    I: Stream,
+{
+    fn parse(&self, input: &mut Input<'a, I>) -> Result<(), Error> {
+        let marker = input.save();
+        let result = self.parser.parse(input);
+        input.rewind(marker);
+        input.release(marker);
+        match result {
+            Ok(_) => {
+                let found = input.source.found(input.pos);
+                let message = match &found {
+                    Some(found) => format!("unexpected {:?}", found),
+                    None => "unexpected end of input".to_string(),
+                };
+                Err(Error::custom(input.pos, message, found))
+            }
+            Err(_) => Ok(()),
         }
+    }
+}
+
+/// See [Parser::labelled].
+#[derive(Clone)]
+pub struct Labelled<P> {
+    pub(crate) parser: P,
+    pub(crate) label: &'static str,
+}
 
-        Ok(items)
+impl<'a, I, O, P> Parser<'a, I, O> for Labelled<P>
+where
+    P: Parser<'a, I, O>,
+{
+    fn parse(&self, input: &mut Input<'a, I>) -> Result<O, Error> {
+        let start = input.pos;
+        self.parser.parse(input).map_err(|mut e| {
+            if e.pos == start && e.message.is_none() {
+                e.expected = [Expected::Label(self.label)].into();
+            } else {
+                e.context.push(self.label);
+            }
+            e
+        })
     }
 }
+
+/// A parser that matches both of its child parsers in sequence, keeping only the output of the
+/// first. See [`Parser::then_ignore`].
+pub type ThenIgnore<A, B, OA, OB> = Map<Then<A, B>, fn((OA, OB)) -> OA, (OA, OB)>;
+
+/// A parser that matches both of its child parsers in sequence, keeping only the output of the
+/// second. See [`Parser::ignore_then`].
+pub type IgnoreThen<A, B, OA, OB> = Map<Then<A, B>, fn((OA, OB)) -> OB, (OA, OB)>;
+
+/// A parser that matches its child parser between two delimiters, keeping only the output of the
+/// child. See [`Parser::delimited_by`].
+pub type DelimitedBy<P, L, R, O, OL, OR> =
+    Map<Then<Then<L, P>, R>, fn(((OL, O), OR)) -> O, ((OL, O), OR)>;
+
+/// See [`choice`].
+#[derive(Clone)]
+pub struct Choice<T> {
+    pub(crate) parsers: T,
+}
+
+/// Try one alternative of a [`choice`], returning its result if no further alternatives should
+/// be tried, or recording its error otherwise.
+fn try_alternative<'a, I, O, P>(
+    input: &mut Input<'a, I>,
+    parser: &P,
+    error: &mut Option<Error>,
+) -> Option<Result<O, Error>>
+where
+    P: Parser<'a, I, O>,
+    I: Stream,
+{
+    match input.attempt(|input| parser.parse(input)) {
+        Ok(o) => {
+            if let Some(e) = error.take() {
+                input.recover(e);
+            }
+            Some(Ok(o))
+        }
+        Err(e) => {
+            let committed = e.committed;
+            let e = match error.take() {
+                Some(error) => error.merge(e),
+                None => e,
+            };
+            if committed {
+                Some(Err(e))
+            } else {
+                *error = Some(e);
+                None
+            }
+        }
+    }
+}
+
+/// Try each of the given parsers in turn, as in [`choice`].
+fn choose<'a, I, O, P>(input: &mut Input<'a, I>, parsers: &[P]) -> Result<O, Error>
+where
+    P: Parser<'a, I, O>,
+    I: Stream,
+{
+    let mut error = None;
+    for parser in parsers {
+        if let Some(result) = try_alternative(input, parser, &mut error) {
+            return result;
+        }
+    }
+    Err(error.unwrap_or_else(|| input.error([])))
+}
+
+impl<'a, I, O, P> Parser<'a, I, O> for Choice<Vec<P>>
+where
+    P: Parser<'a, I, O>,
+    I: Stream,
+{
+    fn parse(&self, input: &mut Input<'a, I>) -> Result<O, Error> {
+        choose(input, &self.parsers)
+    }
+}
+
+impl<'a, I, O, P, const N: usize> Parser<'a, I, O> for Choice<[P; N]>
+where
+    P: Parser<'a, I, O>,
+    I: Stream,
+{
+    fn parse(&self, input: &mut Input<'a, I>) -> Result<O, Error> {
+        choose(input, &self.parsers)
+    }
+}
+
+/// Create a parser that tries each of the given parsers in turn, using the first that succeeds.
+///
+/// The parsers may be given as a tuple of up to 12 parsers of different types, or as a `Vec` or
+/// array of parsers of the same type, such as [`BoxedParser`](crate::boxed::BoxedParser)s. This
+/// behaves like chaining the parsers with [`Parser::or`]: if a parser fails after a
+/// [`Parser::cut`], no later parsers are tried, and if every parser fails, their errors are
+/// merged.
+///
+/// # Example
+/// ```
+/// use aocparse::{combinator::choice, text::just, Parser};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// enum Shape {
+///     Rock,
+///     Paper,
+///     Scissors,
+/// }
+///
+/// let shape = choice((
+///     just("A").to(Shape::Rock),
+///     just("B").to(Shape::Paper),
+///     just("C").to(Shape::Scissors),
+/// ));
+/// assert_eq!(shape.parse_str("B"), Ok(Shape::Paper));
+///
+/// let digits = ["one", "two", "three"].map(|word| just(word).to_slice());
+/// assert_eq!(choice(digits).parse_str("three"), Ok("three"));
+/// ```
+pub fn choice<T>(parsers: T) -> Choice<T> {
+    Choice { parsers }
+}
+
+/// See [`seq`].
+#[derive(Clone)]
+pub struct Seq<T> {
+    pub(crate) parsers: T,
+}
+
+/// Create a parser that matches each of the given parsers in sequence, producing a flat tuple
+/// of their outputs.
+///
+/// The parsers are given as a tuple of up to 12 parsers. This behaves like chaining the parsers
+/// with [`Parser::then`], but avoids the nested tuples that produces.
+///
+/// # Example
+/// ```
+/// use aocparse::{combinator::seq, text::{just, number}, Parser};
+///
+/// let cube = seq((
+///     number::<u32>(10),
+///     just(","),
+///     number::<u32>(10),
+///     just(","),
+///     number::<u32>(10),
+/// ))
+/// .map(|(x, _, y, _, z)| (x, y, z));
+/// assert_eq!(cube.parse_str("2,1,5"), Ok((2, 1, 5)));
+/// ```
+pub fn seq<T>(parsers: T) -> Seq<T> {
+    Seq { parsers }
+}
+
+macro_rules! tuple_impl {
+    ($p0:ident $o0:ident $(, $p:ident $o:ident)*) => {
+        impl<'a, I, O, $p0 $(, $p)*> Parser<'a, I, O> for Choice<($p0, $($p,)*)>
+        where
+            $p0: Parser<'a, I, O>,
+            $($p: Parser<'a, I, O>,)*
+            I: Stream,
+        {
+            #[allow(non_snake_case)]
+            fn parse(&self, input: &mut Input<'a, I>) -> Result<O, Error> {
+                let ($p0, $($p,)*) = &self.parsers;
+                let mut error = None;
+                if let Some(result) = try_alternative(input, $p0, &mut error) {
+                    return result;
+                }
+                $(
+                    if let Some(result) = try_alternative(input, $p, &mut error) {
+                        return result;
+                    }
+                )*
+                Err(error.unwrap_or_else(|| input.error([])))
+            }
+        }
+
+        impl<'a, I, $p0, $o0 $(, $p, $o)*> Parser<'a, I, ($o0, $($o,)*)> for Seq<($p0, $($p,)*)>
+        where
+            $p0: Parser<'a, I, $o0>,
+            $($p: Parser<'a, I, $o>,)*
+            I: Stream,
+        {
+            #[allow(non_snake_case)]
+            fn parse(&self, input: &mut Input<'a, I>) -> Result<($o0, $($o,)*), Error> {
+                let ($p0, $($p,)*) = &self.parsers;
+                input.attempt(|input| {
+                    let $o0 = $p0.parse(input)?;
+                    $(let $o = $p.parse(input).map_err(|e| input.merge_alt(e))?;)*
+                    Ok(($o0, $($o,)*))
+                })
+            }
+        }
+    };
+}
+
+tuple_impl!(A OA);
+tuple_impl!(A OA, B OB);
+tuple_impl!(A OA, B OB, C OC);
+tuple_impl!(A OA, B OB, C OC, D OD);
+tuple_impl!(A OA, B OB, C OC, D OD, E OE);
+tuple_impl!(A OA, B OB, C OC, D OD, E OE, F OF);
+tuple_impl!(A OA, B OB, C OC, D OD, E OE, F OF, G OG);
+tuple_impl!(A OA, B OB, C OC, D OD, E OE, F OF, G OG, H OH);
+tuple_impl!(A OA, B OB, C OC, D OD, E OE, F OF, G OG, H OH, J OJ);
+tuple_impl!(A OA, B OB, C OC, D OD, E OE, F OF, G OG, H OH, J OJ, K OK);
+tuple_impl!(A OA, B OB, C OC, D OD, E OE, F OF, G OG, H OH, J OJ, K OK, L OL);
+tuple_impl!(A OA, B OB, C OC, D OD, E OE, F OF, G OG, H OH, J OJ, K OK, L OL, M OM);
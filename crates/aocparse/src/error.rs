@@ -0,0 +1,193 @@
+//! Defines the error type produced by parsers.
+
+use std::{collections::BTreeSet, fmt};
+
+use crate::report::Report;
+
+/// Something that a parser expected to find at a given position.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Expected {
+    /// A literal token, as matched by [`just`](crate::text::just).
+    Just(&'static str),
+    /// A single character.
+    Char(char),
+    /// A single byte, as matched by [`byte`](crate::bytes::byte).
+    Byte(u8),
+    /// A literal sequence of bytes, as matched by [`bytes`](crate::bytes::bytes).
+    Bytes(&'static [u8]),
+    /// A single token, as matched by [`token::just`](crate::token::just).
+    Token(String),
+    /// Any character except those in the given string.
+    NoneOf(&'static str),
+    /// A description of a class of input, such as "a digit".
+    Description(&'static str),
+    /// The end of the input.
+    End,
+    /// A friendly name given to a parser using [`Parser::labelled`](crate::Parser::labelled).
+    Label(&'static str),
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expected::Just(token) => write!(f, "`just({:?})`", token),
+            Expected::Char(c) => write!(f, "{:?}", c),
+            Expected::Byte(byte) => write!(f, "`byte({:#04x})`", byte),
+            Expected::Token(token) => f.write_str(token),
+            Expected::Bytes(bytes) => write!(f, "`bytes(b\"{}\")`", bytes.escape_ascii()),
+            Expected::NoneOf(chars) => write!(f, "any character except {:?}", chars),
+            Expected::Description(description) => f.write_str(description),
+            Expected::End => f.write_str("end of input"),
+            Expected::Label(label) => f.write_str(label),
+        }
+    }
+}
+
+/// An error produced when a parser fails.
+///
+/// Errors record the byte offset at which parsing failed, the set of things that were
+/// expected at that offset, and what was actually found there.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Error {
+    pub(crate) pos: usize,
+    pub(crate) expected: BTreeSet<Expected>,
+    pub(crate) found: Option<String>,
+    /// A custom message describing the error, replacing the expected-vs-found message.
+    pub(crate) message: Option<String>,
+    /// The labels of the parsers that the error occurred within, innermost first.
+    pub(crate) context: Vec<&'static str>,
+    /// Whether the error occurred after a [`Parser::cut`](crate::Parser::cut), preventing
+    /// backtracking.
+    pub(crate) committed: bool,
+}
+
+impl Error {
+    /// Create a new error at the given position.
+    ///
+    /// A `found` value of `None` indicates that the end of the input was reached.
+    pub fn new<E>(pos: usize, expected: E, found: Option<String>) -> Self
+    where
+        E: IntoIterator<Item = Expected>,
+    {
+        Error {
+            pos,
+            expected: expected.into_iter().collect(),
+            found,
+            message: None,
+            context: Vec::new(),
+            committed: false,
+        }
+    }
+
+    /// Create a new error at the given position with a custom message.
+    ///
+    /// This is useful for reporting semantic errors, such as from [`Parser::try_map`](crate::Parser::try_map).
+    pub fn custom<M>(pos: usize, message: M, found: Option<String>) -> Self
+    where
+        M: ToString,
+    {
+        Error {
+            message: Some(message.to_string()),
+            ..Error::new(pos, [], found)
+        }
+    }
+
+    /// The byte offset at which the error occurred.
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// The things that were expected at the error position.
+    pub fn expected(&self) -> impl Iterator<Item = &Expected> {
+        self.expected.iter()
+    }
+
+    /// What was found at the error position, or `None` if the end of the input was reached.
+    pub fn found(&self) -> Option<&str> {
+        self.found.as_deref()
+    }
+
+    /// The custom message of the error, if it has one.
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+
+    /// The labels of the parsers that the error occurred within, outermost first.
+    ///
+    /// See [`Parser::labelled`](crate::Parser::labelled).
+    pub fn context(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.context.iter().rev().copied()
+    }
+
+    /// Whether the error occurred after a [`Parser::cut`](crate::Parser::cut), meaning that
+    /// enclosing parsers will not backtrack to try alternatives.
+    pub fn is_committed(&self) -> bool {
+        self.committed
+    }
+
+    /// Merge this error with another.
+    ///
+    /// The error that occurred furthest into the input wins. If both errors occurred at the
+    /// same position, their expected sets are combined. The merged error is committed if
+    /// either error was.
+    pub fn merge(mut self, other: Error) -> Error {
+        let committed = self.committed || other.committed;
+        let mut merged = match self.pos.cmp(&other.pos) {
+            std::cmp::Ordering::Less => other,
+            std::cmp::Ordering::Greater => self,
+            std::cmp::Ordering::Equal => {
+                self.expected.extend(other.expected);
+                self.message = self.message.or(other.message);
+                if self.context.is_empty() {
+                    self.context = other.context;
+                }
+                self
+            }
+        };
+        merged.committed = committed;
+        merged
+    }
+
+    /// Create a human-readable report of this error against the given source.
+    ///
+    /// See [`Report`] for details.
+    pub fn report<'e, 's>(&'e self, source: &'s str) -> Report<'e, 's> {
+        Report::new(self, source)
+    }
+
+    /// Write the expected-vs-found message, without position information.
+    pub(crate) fn fmt_message(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for label in self.context() {
+            write!(f, "in {} > ", label)?;
+        }
+        if let Some(message) = &self.message {
+            return f.write_str(message);
+        }
+        let mut expected = self.expected.iter();
+        if let Some(first) = expected.next() {
+            write!(f, "expected {}", first)?;
+            let rest: Vec<_> = expected.collect();
+            for (i, e) in rest.iter().enumerate() {
+                if i + 1 == rest.len() {
+                    write!(f, " or {}", e)?;
+                } else {
+                    write!(f, ", {}", e)?;
+                }
+            }
+            f.write_str(", ")?;
+        }
+        match &self.found {
+            Some(found) => write!(f, "found {:?}", found),
+            None => f.write_str("found end of input"),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "at offset {}: ", self.pos)?;
+        self.fmt_message(f)
+    }
+}
+
+impl std::error::Error for Error {}
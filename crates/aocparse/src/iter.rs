@@ -0,0 +1,109 @@
+//! Defines an iterator that parses records from an input one at a time.
+
+use std::marker::PhantomData;
+
+use crate::{Error, Expected, Input, Parser, Stream};
+
+/// See [`Parser::parse_iter`].
+pub struct ParseIter<'a, P, S, I, O, OS> {
+    pub(crate) parser: P,
+    pub(crate) separator: S,
+    pub(crate) input: Input<'a, I>,
+    /// Whether a record has been parsed, so that a separator is expected before the next one.
+    pub(crate) started: bool,
+    /// Whether the input is exhausted, or parsing has failed.
+    pub(crate) done: bool,
+    pub(crate) __phantom: PhantomData<fn() -> (O, OS)>,
+}
+
+impl<'a, P, S, I, O, OS> ParseIter<'a, P, S, I, O, OS> {
+    /// Expect the given separator between records.
+    ///
+    /// The separator may also appear once after the last record.
+    pub fn separator<T, OT>(self, separator: T) -> ParseIter<'a, P, T, I, O, OT>
+    where
+        T: Parser<'a, I, OT>,
+    {
+        ParseIter {
+            parser: self.parser,
+            separator,
+            input: self.input,
+            started: self.started,
+            done: self.done,
+            __phantom: PhantomData,
+        }
+    }
+
+    /// The input being parsed, positioned after the last record or separator that was parsed.
+    pub fn input(&self) -> &Input<'a, I> {
+        &self.input
+    }
+}
+
+impl<'a, P, S, I, O, OS> ParseIter<'a, P, S, I, O, OS>
+where
+    P: Parser<'a, I, O>,
+    S: Parser<'a, I, OS>,
+    I: Stream,
+{
+    /// Parse the next separator and record, or produce `None` at the end of the input.
+    fn step(&mut self) -> Result<Option<O>, Error> {
+        let input = &mut self.input;
+        let start = input.pos;
+        if self.started {
+            if let Err(e) = self.separator.parse(input) {
+                if input.source.next(input.pos).is_none() {
+                    return Ok(None);
+                }
+                let found = input.source.found(input.pos);
+                let end = Error::new(input.pos, [Expected::End], found);
+                return Err(input.merge_alt(e.merge(end)));
+            }
+        }
+        if input.source.next(input.pos).is_none() {
+            return Ok(None);
+        }
+        let output = self.parser.parse(input)?;
+        if self.started && input.pos == start {
+            let found = input.source.found(input.pos);
+            return Err(Error::custom(
+                input.pos,
+                "expected records to consume input",
+                found,
+            ));
+        }
+        self.started = true;
+        Ok(Some(output))
+    }
+}
+
+impl<'a, P, S, I, O, OS> Iterator for ParseIter<'a, P, S, I, O, OS>
+where
+    P: Parser<'a, I, O>,
+    S: Parser<'a, I, OS>,
+    I: Stream,
+{
+    type Item = Result<O, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        // save and release each record's starting position, so that streams can drop the input
+        // before it
+        let marker = self.input.save();
+        let result = self.step();
+        self.input.release(marker);
+        match result {
+            Ok(Some(output)) => Some(Ok(output)),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
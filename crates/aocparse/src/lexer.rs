@@ -0,0 +1,154 @@
+//! Defines a lexer, which splits text into tokens before parsing.
+
+use std::rc::Rc;
+
+use crate::{span::Span, DynParser, Error, Input, Parser};
+
+/// A rule in a lexer's token table, producing `None` for input that is skipped.
+type Rule<'a, T> = Rc<dyn DynParser<'a, &'a str, Option<T>> + 'a>;
+
+/// See [`lexer`].
+pub struct Lexer<'a, T> {
+    rules: Vec<Rule<'a, T>>,
+}
+
+impl<T> Clone for Lexer<'_, T> {
+    fn clone(&self) -> Self {
+        Lexer {
+            rules: self.rules.clone(),
+        }
+    }
+}
+
+impl<'a, T: 'a> Lexer<'a, T> {
+    /// Add a rule that produces a token from the input matched by the given parser.
+    pub fn token<P>(mut self, parser: P) -> Self
+    where
+        P: Parser<'a, &'a str, T> + 'a,
+    {
+        self.rules.push(Rc::new(parser.map(Some)));
+        self
+    }
+
+    /// Add a rule that skips the input matched by the given parser, such as whitespace or
+    /// comments.
+    pub fn skip<P, O>(mut self, parser: P) -> Self
+    where
+        P: Parser<'a, &'a str, O> + 'a,
+        O: 'a,
+    {
+        self.rules.push(Rc::new(parser.ignored().map(|()| None)));
+        self
+    }
+
+    /// Split the whole of the given source into tokens, along with the span of source text
+    /// that each token was produced from.
+    pub fn lex(&self, source: &'a str) -> Result<Vec<(T, Span)>, Error> {
+        self.parse_str_all(source)
+    }
+
+    /// Find the rule that matches the longest non-empty prefix of the input, preferring
+    /// earlier rules when several match the same length.
+    fn longest_match(&self, input: &mut Input<'a, &'a str>) -> Result<Option<Option<T>>, Error> {
+        let start = input.save();
+        let mut best = None;
+        let mut error: Option<Error> = None;
+        for rule in &self.rules {
+            match rule.parse_dyn(input) {
+                Ok(output) if input.pos > best.as_ref().map_or(start.pos, |(end, _, _)| *end) => {
+                    let diagnostics = input.diagnostics.split_off(start.diagnostics);
+                    best = Some((input.pos, output, diagnostics));
+                }
+                Ok(_) => {}
+                Err(e) if e.committed => {
+                    input.rewind(start);
+                    input.release(start);
+                    return Err(e);
+                }
+                Err(e) => {
+                    error = Some(match error {
+                        Some(error) => error.merge(e),
+                        None => e,
+                    });
+                }
+            }
+            input.rewind(start);
+        }
+        input.release(start);
+        match best {
+            Some((end, output, diagnostics)) => {
+                input.pos = end;
+                input.diagnostics.extend(diagnostics);
+                Ok(Some(output))
+            }
+            None => {
+                if let Some(error) = error {
+                    input.recover(error);
+                }
+                Ok(None)
+            }
+        }
+    }
+}
+
+impl<'a, T: 'a> Parser<'a, &'a str, Vec<(T, Span)>> for Lexer<'a, T> {
+    fn parse(&self, input: &mut Input<'a, &'a str>) -> Result<Vec<(T, Span)>, Error> {
+        let mut tokens = Vec::new();
+        loop {
+            let start = input.pos;
+            match self.longest_match(input)? {
+                Some(Some(token)) => tokens.push((token, Span::new(start, input.pos))),
+                Some(None) => {}
+                None => return Ok(tokens),
+            }
+        }
+    }
+}
+
+/// Create a lexer with an empty token table.
+///
+/// Rules are added using [`Lexer::token`] and [`Lexer::skip`]. At each position, the rule that
+/// matches the longest input is used, with ties going to the rule that was added first. Rules
+/// that match no input are ignored. Lexing stops at the first position no rule matches, so
+/// [`Lexer::lex`] fails if there is any input left over.
+///
+/// The tokens can then be parsed using the primitives in [`token`](crate::token).
+///
+/// # Example
+/// ```
+/// use aocparse::{
+///     lexer::lexer,
+///     span::Span,
+///     text::{ident, just, number, whitespace},
+///     Parser,
+/// };
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// enum Tok<'a> {
+///     Num(u32),
+///     Ident(&'a str),
+///     Let,
+///     Eq,
+/// }
+///
+/// let lexer = lexer()
+///     .token(just("let").to(Tok::Let))
+///     .token(ident().map(Tok::Ident))
+///     .token(number(10).map(Tok::Num))
+///     .token(just("=").to(Tok::Eq))
+///     .skip(whitespace());
+///
+/// assert_eq!(
+///     lexer.lex("let letter = 42"),
+///     Ok(vec![
+///         (Tok::Let, Span::new(0, 3)),
+///         (Tok::Ident("letter"), Span::new(4, 10)),
+///         (Tok::Eq, Span::new(11, 12)),
+///         (Tok::Num(42), Span::new(13, 15)),
+///     ])
+/// );
+/// assert!(lexer.lex("let x = ?").is_err());
+/// ```
+pub fn lexer<'a, T>() -> Lexer<'a, T> {
+    Lexer { rules: Vec::new() }
+}
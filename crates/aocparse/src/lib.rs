@@ -1,39 +1,345 @@
 //! Generic parsing utilities for Advent of Code.
 //!
-//! This crate is heavily inspired by the [chumsky](https://github.com/zesterer/chumsky) crate, being 
+//! This crate is heavily inspired by the [chumsky](https://github.com/zesterer/chumsky) crate, being
 //! a parser combinator library.
 
-use std::marker::PhantomData;
+use std::{marker::PhantomData, rc::Rc, sync::Arc};
 
-use combinator::{DelimitedBy, Foldl, Maybe, Or, Repeated, Then};
-use primitive::{Ignored, Map, To};
+use boxed::{BoxedParser, SyncBoxedParser};
+use combinator::{
+    AndIs, Cut, DelimitedBy, Foldl, IgnoreThen, Labelled, Maybe, Not, Or, Repeated, Rewind,
+    SeparatedBy, Then, ThenIgnore,
+};
+use iter::ParseIter;
+use memo::Memoized;
+use primitive::{
+    empty, end, Empty, Filter, Ignored, Map, MapWithSpan, To, ToSlice, TryMap, Validate, WithSpan,
+};
+use span::{Span, Spanned};
 
-mod primitive;
+pub use error::{Error, Expected};
 
+pub mod bits;
+pub mod boxed;
+pub mod bytes;
 pub mod combinator;
+pub mod error;
+pub mod iter;
+pub mod lexer;
+pub mod memo;
+mod parallel;
+pub mod pratt;
+pub mod primitive;
+pub mod reader;
+pub mod recursive;
+pub mod report;
+pub mod span;
 pub mod text;
+pub mod token;
 
 /// The input for a parser.
 ///
-/// This is a generic data structure, currently able to accept string slices
-/// as input.
+/// This is a generic data structure, able to accept string slices, byte slices,
+/// [`Bits`](bits::Bits), slices of [tokens](token::Token) and text read from a
+/// [`Reader`](reader::Reader) as input.
 ///
 /// # Example
 /// ```
-/// let input = "1234567890";
-/// parser.parse(input.into());
+/// use aocparse::{text::number, Input, Parser};
+///
+/// let mut input = Input::from("1234567890");
+/// assert_eq!(number::<u64>(10).parse(&mut input), Ok(1234567890));
 /// ```
 pub struct Input<'a, I> {
     pub(crate) pos: usize,
     pub(crate) source: I,
+    /// The furthest error that was recovered from, used to enrich later errors.
+    pub(crate) alt: Option<Error>,
+    /// Non-fatal errors recorded by [`Parser::validate`].
+    pub(crate) diagnostics: Vec<Error>,
+    /// A unique identifier for this input, used to key memoized results.
+    pub(crate) id: usize,
+    /// The position of the source within a larger input, which is added to spans.
+    pub(crate) offset: usize,
     pub(crate) __phantom: PhantomData<&'a I>,
 }
 
-impl<'a> From<&'a str> for Input<'a, &'a str> {
-    fn from(source: &'a str) -> Self {
+/// A saved position in an [`Input`], used to backtrack after a parser fails.
+#[derive(Clone, Copy)]
+pub(crate) struct Marker {
+    pos: usize,
+    diagnostics: usize,
+}
+
+impl<'a, I> Input<'a, I> {
+    /// The current position of the input.
+    ///
+    /// This is a byte offset for strings and byte slices, a bit offset for
+    /// [`Bits`](bits::Bits) and an index for slices of tokens.
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// The stream that this input reads from.
+    pub fn stream(&self) -> &I {
+        &self.source
+    }
+
+    /// The non-fatal errors recorded by [`Parser::validate`] while parsing this input.
+    pub fn diagnostics(&self) -> &[Error] {
+        &self.diagnostics
+    }
+
+    /// Rewind the input to a previously saved position.
+    ///
+    /// Any diagnostics recorded since the position was saved are discarded. The position stays
+    /// saved until it is released, so the input can be rewound to it more than once.
+    pub(crate) fn rewind(&mut self, marker: Marker) {
+        self.pos = marker.pos;
+        self.diagnostics.truncate(marker.diagnostics);
+    }
+
+    /// Record an error that a parser recovered from, such as a failed optional branch.
+    pub(crate) fn recover(&mut self, error: Error) {
+        self.alt = Some(match self.alt.take() {
+            Some(alt) => alt.merge(error),
+            None => error,
+        });
+    }
+
+    /// Merge the given error with the furthest error recovered from so far.
+    pub(crate) fn merge_alt(&self, error: Error) -> Error {
+        match &self.alt {
+            Some(alt) => error.merge(alt.clone()),
+            None => error,
+        }
+    }
+}
+
+impl<'a, I: Stream> Input<'a, I> {
+    /// Save the current position of the input, so that it can be rewound to.
+    ///
+    /// Every saved position must be released once it will no longer be rewound to.
+    pub(crate) fn save(&self) -> Marker {
+        self.source.save(self.pos);
+        Marker {
+            pos: self.pos,
+            diagnostics: self.diagnostics.len(),
+        }
+    }
+
+    /// Release a saved position, once the input will no longer be rewound to it.
+    pub(crate) fn release(&self, marker: Marker) {
+        self.source.release(marker.pos);
+    }
+
+    /// Run the given parse function, rewinding the input if it fails.
+    pub(crate) fn attempt<O, F>(&mut self, f: F) -> Result<O, Error>
+    where
+        F: FnOnce(&mut Self) -> Result<O, Error>,
+    {
+        let marker = self.save();
+        let result = f(self);
+        if result.is_err() {
+            self.rewind(marker);
+        }
+        self.release(marker);
+        result
+    }
+}
+
+impl<'a, I: Source> Input<'a, I> {
+    /// Create an error at the current position.
+    pub(crate) fn error<E>(&self, expected: E) -> Error
+    where
+        E: IntoIterator<Item = Expected>,
+    {
+        Error::new(self.pos, expected, self.source.found(self.pos))
+    }
+}
+
+/// A source of input which parsers can report errors against.
+pub trait Source {
+    /// Describe the item at the given position, or return `None` at the end of the input.
+    fn found(&self, pos: usize) -> Option<String>;
+
+    /// Describe the remaining input from the given position, or return `None` at the end of
+    /// the input.
+    fn rest(&self, pos: usize) -> Option<String> {
+        self.found(pos)
+    }
+}
+
+/// A stream of items which parsers read from.
+///
+/// Positions are opaque offsets into the stream: parsers only ever use positions returned by
+/// [`Stream::next`], or the starting position of `0`. This trait is implemented for string
+/// slices, byte slices, [`Bits`](bits::Bits), slices of [tokens](token::Token) and
+/// [`Reader`](reader::Reader)s, and can be implemented for other sources such as rope buffers.
+/// The primitives in [`text::generic`] can be used with any stream of `char`s, but the numeric
+/// parsers [`text::number`] and [`text::float`] only work with string slices.
+///
+/// # Saving and restoring positions
+/// Parsers backtrack by restoring an earlier position of the input. Before a parser may
+/// restore a position, it passes the position to [`Stream::save`], and once it will no longer
+/// restore it, it passes the position to [`Stream::release`]. Saves and releases are nested,
+/// so a stream that reads its input incrementally only needs to keep the items from the
+/// oldest saved position onwards.
+///
+/// # Example
+/// ```
+/// use aocparse::{text::generic::alpha, Input, Parser, Source, Stream};
+///
+/// /// Text stored as a list of chunks.
+/// struct Chunks<'a>(&'a [&'a str]);
+///
+/// impl Chunks<'_> {
+///     /// Find the chunk and offset of a position, where positions count characters.
+///     fn find(&self, mut pos: usize) -> Option<char> {
+///         for chunk in self.0 {
+///             match chunk.chars().nth(pos) {
+///                 Some(c) => return Some(c),
+///                 None => pos -= chunk.chars().count(),
+///             }
+///         }
+///         None
+///     }
+/// }
+///
+/// impl Source for Chunks<'_> {
+///     fn found(&self, pos: usize) -> Option<String> {
+///         self.find(pos).map(String::from)
+///     }
+/// }
+///
+/// impl Stream for Chunks<'_> {
+///     type Item = char;
+///     type Slice = String;
+///     type Span = std::ops::Range<usize>;
+///
+///     fn next(&self, pos: usize) -> Option<(char, usize)> {
+///         self.find(pos).map(|c| (c, pos + 1))
+///     }
+///
+///     fn slice(&self, start: usize, end: usize) -> String {
+///         (start..end).filter_map(|pos| self.find(pos)).collect()
+///     }
+///
+///     fn span(&self, start: usize, end: usize) -> Self::Span {
+///         start..end
+///     }
+/// }
+///
+/// let word = alpha().repeated().map(|letters| letters.concat());
+/// let mut input = Input::from(Chunks(&["he", "", "llo", " world"]));
+/// assert_eq!(word.parse(&mut input), Ok("hello".to_string()));
+/// ```
+pub trait Stream: Source {
+    /// The type of the items in the stream, such as `char` for text.
+    type Item;
+    /// The type of a run of consecutive items, such as `&str` for text.
+    type Slice;
+    /// The type describing a region of the stream.
+    type Span;
+
+    /// Get the item at the given position, along with the position of the item after it, or
+    /// return `None` at the end of the stream.
+    fn next(&self, pos: usize) -> Option<(Self::Item, usize)>;
+
+    /// Get the items from the start position up to, but not including, the end position.
+    fn slice(&self, start: usize, end: usize) -> Self::Slice;
+
+    /// Get the span from the start position up to, but not including, the end position.
+    fn span(&self, start: usize, end: usize) -> Self::Span;
+
+    /// Record that parsers may restore the given position.
+    ///
+    /// The default implementation does nothing.
+    fn save(&self, pos: usize) {
+        let _ = pos;
+    }
+
+    /// Record that parsers will no longer restore a position previously passed to
+    /// [`Stream::save`].
+    ///
+    /// The default implementation does nothing.
+    fn release(&self, pos: usize) {
+        let _ = pos;
+    }
+}
+
+impl Source for &str {
+    fn found(&self, pos: usize) -> Option<String> {
+        self.get(pos..)
+            .and_then(|rest| rest.chars().next())
+            .map(String::from)
+    }
+
+    fn rest(&self, pos: usize) -> Option<String> {
+        self.get(pos..)
+            .filter(|rest| !rest.is_empty())
+            .map(String::from)
+    }
+}
+
+impl Source for &[u8] {
+    fn found(&self, pos: usize) -> Option<String> {
+        self.get(pos).map(|byte| byte.escape_ascii().to_string())
+    }
+
+    fn rest(&self, pos: usize) -> Option<String> {
+        self.get(pos..)
+            .filter(|rest| !rest.is_empty())
+            .map(|rest| rest.escape_ascii().to_string())
+    }
+}
+
+impl<'a> Stream for &'a [u8] {
+    type Item = u8;
+    type Slice = &'a [u8];
+    type Span = Span;
+
+    fn next(&self, pos: usize) -> Option<(u8, usize)> {
+        self.get(pos).map(|byte| (*byte, pos + 1))
+    }
+
+    fn slice(&self, start: usize, end: usize) -> &'a [u8] {
+        self.get(start..end).unwrap_or_default()
+    }
+
+    fn span(&self, start: usize, end: usize) -> Span {
+        Span::new(start, end)
+    }
+}
+
+impl<'a> Stream for &'a str {
+    type Item = char;
+    type Slice = &'a str;
+    type Span = Span;
+
+    fn next(&self, pos: usize) -> Option<(char, usize)> {
+        let c = self.get(pos..)?.chars().next()?;
+        Some((c, pos + c.len_utf8()))
+    }
+
+    fn slice(&self, start: usize, end: usize) -> &'a str {
+        self.get(start..end).unwrap_or_default()
+    }
+
+    fn span(&self, start: usize, end: usize) -> Span {
+        Span::new(start, end)
+    }
+}
+
+impl<'a, S: Stream + 'a> From<S> for Input<'a, S> {
+    fn from(source: S) -> Self {
         Input {
             pos: 0,
             source,
+            alt: None,
+            diagnostics: Vec::new(),
+            id: memo::next_input_id(),
+            offset: 0,
             __phantom: PhantomData,
         }
     }
@@ -57,19 +363,82 @@ pub trait Parser<'a, I, O>: Clone {
     }
 
     /// Maps the result of this parser to the desired output.
+    ///
+    /// The function is shared between clones of the parser rather than cloned, so it can
+    /// capture data such as lookup tables. The same goes for the functions given to
+    /// [`Parser::filter`], [`Parser::try_map`], [`Parser::validate`] and [`Parser::foldl`].
+    ///
+    /// # Example
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use aocparse::{text::alpha, Parser};
+    ///
+    /// let values = HashMap::from([("a", 1), ("b", 2)]);
+    /// let value = alpha().map(move |name: &str| values[name]);
+    /// let values = value.clone().then(value);
+    /// assert_eq!(values.parse_str("ba"), Ok((2, 1)));
+    /// ```
     fn map<T, F>(self, f: F) -> Map<Self, F, O>
     where
         Self: Sized,
         F: Fn(O) -> T,
     {
         Map {
-            f,
+            f: Arc::new(f),
             parser: self,
             __phantom: PhantomData,
         }
     }
 
+    /// Maps the result of this parser to the desired output, along with the span of input
+    /// that it was parsed from.
+    ///
+    /// # Example
+    /// ```
+    /// use aocparse::{span::Span, text::{just, number}, Parser};
+    ///
+    /// let parser = just("#").then(number::<u32>(10).map_with_span(|n, span: Span| (n, span.range())));
+    /// assert_eq!(parser.parse_str("#123"), Ok(((), (123, 1..4))));
+    /// ```
+    fn map_with_span<T, F>(self, f: F) -> MapWithSpan<Self, F, O>
+    where
+        Self: Sized,
+        I: Stream,
+        F: Fn(O, I::Span) -> T,
+    {
+        MapWithSpan {
+            f: Arc::new(f),
+            parser: self,
+            __phantom: PhantomData,
+        }
+    }
+
+    /// Wrap the result of this parser in a [`Spanned`], recording the span of input that it
+    /// was parsed from.
+    ///
+    /// # Example
+    /// ```
+    /// use aocparse::{span::Span, text::{number, whitespace}, Parser};
+    ///
+    /// let numbers = number::<u32>(10).spanned().separated_by(whitespace());
+    /// let numbers = numbers.parse_str("467 35").unwrap();
+    /// assert_eq!(*numbers[1].value(), 35);
+    /// assert_eq!(numbers[1].span(), Span::new(4, 6));
+    /// ```
+    fn spanned(self) -> WithSpan<Self, O, I::Span>
+    where
+        Self: Sized,
+        I: Stream,
+    {
+        self.map_with_span(Spanned::new)
+    }
+
     /// Use this parser, or another if this one fails.
+    ///
+    /// The input is rewound before the other parser is tried, so both parsers see the same
+    /// input. If this parser fails after a [`Parser::cut`], the other parser is not tried.
+    /// To choose between many parsers, see [`choice`](combinator::choice).
     fn or<B>(self, other: B) -> Or<Self, B>
     where
         Self: Sized,
@@ -78,6 +447,85 @@ pub trait Parser<'a, I, O>: Clone {
         Or { a: self, b: other }
     }
 
+    /// Only accept the output of this parser if it satisfies the given predicate.
+    ///
+    /// If the predicate fails, the parser fails with a custom error positioned at the start of
+    /// this parser's input. Use [`Parser::try_map`] for a more specific message.
+    ///
+    /// # Example
+    /// ```
+    /// use aocparse::{text::number, Parser};
+    ///
+    /// let even = number::<u32>(10).filter(|n| n % 2 == 0);
+    /// assert_eq!(even.parse_str("42"), Ok(42));
+    ///
+    /// let error = even.parse_str("43").unwrap_err();
+    /// assert_eq!(error.to_string(), "at offset 0: value did not satisfy filter");
+    /// ```
+    fn filter<F>(self, f: F) -> Filter<Self, F>
+    where
+        Self: Sized,
+        F: Fn(&O) -> bool,
+    {
+        Filter {
+            parser: self,
+            f: Arc::new(f),
+        }
+    }
+
+    /// Map the output of this parser using a function that may fail.
+    ///
+    /// If the function returns an error, the parser fails with a custom error using the
+    /// error's message, positioned at the start of this parser's input.
+    ///
+    /// # Example
+    /// ```
+    /// use aocparse::{text::number, Parser};
+    ///
+    /// let byte = number::<u32>(10).try_map(u8::try_from);
+    /// assert_eq!(byte.parse_str("255"), Ok(255));
+    /// assert!(byte.parse_str("256").is_err());
+    /// ```
+    fn try_map<T, E, F>(self, f: F) -> TryMap<Self, F, O>
+    where
+        Self: Sized,
+        F: Fn(O) -> Result<T, E>,
+    {
+        TryMap {
+            parser: self,
+            f: Arc::new(f),
+            __phantom: PhantomData,
+        }
+    }
+
+    /// Validate the output of this parser, recording a non-fatal diagnostic if it is invalid.
+    ///
+    /// Unlike [`Parser::try_map`], the output is kept and parsing continues. Diagnostics can be
+    /// retrieved using [`Input::diagnostics`].
+    ///
+    /// # Example
+    /// ```
+    /// use aocparse::{text::number, Input, Parser};
+    ///
+    /// let byte = number::<u32>(10).validate(|n| match *n {
+    ///     0..=255 => Ok(()),
+    ///     _ => Err(format!("{} is out of range", n)),
+    /// });
+    /// let mut input = Input::from("300");
+    /// assert_eq!(byte.parse(&mut input), Ok(300));
+    /// assert_eq!(input.diagnostics()[0].message(), Some("300 is out of range"));
+    /// ```
+    fn validate<E, F>(self, f: F) -> Validate<Self, F>
+    where
+        Self: Sized,
+        F: Fn(&O) -> Result<(), E>,
+    {
+        Validate {
+            parser: self,
+            f: Arc::new(f),
+        }
+    }
+
     /// Map the output of this parser to the given constant value.
     fn to<T>(self, value: T) -> To<Self, O, T>
     where
@@ -90,7 +538,34 @@ pub trait Parser<'a, I, O>: Clone {
         }
     }
 
+    /// Discard the output of this parser, producing the slice of input that it consumed
+    /// instead.
+    ///
+    /// For string and byte slices, this borrows the matched input without allocating, however
+    /// many sub-parsers produced it.
+    ///
+    /// # Example
+    /// ```
+    /// use aocparse::{text::{alpha, digit, just}, Parser};
+    ///
+    /// let word = alpha().then(alpha().or(digit(10)).repeated()).to_slice();
+    /// let words = word.separated_by(just(", "));
+    /// assert_eq!(words.parse_str("a1, bc, d23"), Ok(vec!["a1", "bc", "d23"]));
+    /// ```
+    fn to_slice(self) -> ToSlice<Self, O>
+    where
+        Self: Sized,
+        I: Stream,
+    {
+        ToSlice {
+            parser: self,
+            __phantom: PhantomData,
+        }
+    }
+
     /// Use this parser, then another.
+    ///
+    /// To match many parsers in sequence, see [`seq`](combinator::seq).
     fn then<B>(self, other: B) -> Then<Self, B>
     where
         Self: Sized,
@@ -98,6 +573,208 @@ pub trait Parser<'a, I, O>: Clone {
         Then { a: self, b: other }
     }
 
+    /// Use this parser, then another, keeping only the output of this parser.
+    ///
+    /// # Example
+    /// ```
+    /// use aocparse::{text::{just, number}, Parser};
+    ///
+    /// let percent = number::<u32>(10).then_ignore(just("%"));
+    /// assert_eq!(percent.parse_str("42%"), Ok(42));
+    /// ```
+    fn then_ignore<B, OB>(self, other: B) -> ThenIgnore<Self, B, O, OB>
+    where
+        Self: Sized,
+        B: Parser<'a, I, OB>,
+    {
+        let first: fn((O, OB)) -> O = |(a, _)| a;
+        Map {
+            parser: Then { a: self, b: other },
+            f: Arc::new(first),
+            __phantom: PhantomData,
+        }
+    }
+
+    /// Use this parser, then another, keeping only the output of the other parser.
+    ///
+    /// # Example
+    /// ```
+    /// use aocparse::{text::{just, number}, Parser};
+    ///
+    /// let game = just("Game ").ignore_then(number::<u32>(10));
+    /// assert_eq!(game.parse_str("Game 7"), Ok(7));
+    /// ```
+    fn ignore_then<B, OB>(self, other: B) -> IgnoreThen<Self, B, O, OB>
+    where
+        Self: Sized,
+        B: Parser<'a, I, OB>,
+    {
+        let second: fn((O, OB)) -> OB = |(_, b)| b;
+        Map {
+            parser: Then { a: self, b: other },
+            f: Arc::new(second),
+            __phantom: PhantomData,
+        }
+    }
+
+    /// Use this parser between two others, such as a pair of brackets, keeping only the output
+    /// of this parser.
+    ///
+    /// # Example
+    /// ```
+    /// use aocparse::{text::{just, number}, Parser};
+    ///
+    /// let list = number::<u32>(10)
+    ///     .separated_by(just(","))
+    ///     .delimited_by(just("["), just("]"));
+    /// assert_eq!(list.parse_str("[1,2,3]"), Ok(vec![1, 2, 3]));
+    /// ```
+    fn delimited_by<L, R, OL, OR>(self, open: L, close: R) -> DelimitedBy<Self, L, R, O, OL, OR>
+    where
+        Self: Sized,
+        L: Parser<'a, I, OL>,
+        R: Parser<'a, I, OR>,
+    {
+        let inner: fn(((OL, O), OR)) -> O = |((_, o), _)| o;
+        Map {
+            parser: Then {
+                a: Then { a: open, b: self },
+                b: close,
+            },
+            f: Arc::new(inner),
+            __phantom: PhantomData,
+        }
+    }
+
+    /// Commit to this parser.
+    ///
+    /// If this parser fails, enclosing combinators such as [`Parser::or`], [`Parser::optional`]
+    /// and [`Parser::repeated`] will not backtrack to try alternatives, and will instead fail
+    /// with this parser's error. This produces better errors once a grammar knows which
+    /// alternative it is in, and bounds the cost of trying alternatives.
+    ///
+    /// # Example
+    /// ```
+    /// use aocparse::{text::{just, number}, Parser};
+    ///
+    /// let game = just("Game ").then(number::<u32>(10).cut()).ignored();
+    /// let parser = game.or(just("Game over"));
+    /// assert!(parser.parse_str("Game over").is_err());
+    /// ```
+    fn cut(self) -> Cut<Self>
+    where
+        Self: Sized,
+    {
+        Cut { parser: self }
+    }
+
+    /// Give this parser a friendly name to use in errors.
+    ///
+    /// If this parser fails without consuming any input, the things it expected are replaced
+    /// with the label. Otherwise, or if the error has a custom message, the label is added to
+    /// the error's context, so that nested labels are reported as a stack, such as
+    /// "in game record > in draw > expected colour".
+    ///
+    /// # Example
+    /// ```
+    /// use aocparse::{text::{just, number}, Parser};
+    ///
+    /// let id = number::<u32>(10).labelled("game id");
+    /// let game = just("Game ").then(id).labelled("game record");
+    /// let error = game.parse_str("Game x").unwrap_err();
+    /// assert_eq!(
+    ///     error.to_string(),
+    ///     "at offset 5: in game record > expected game id, found \"x\""
+    /// );
+    /// ```
+    fn labelled(self, label: &'static str) -> Labelled<Self>
+    where
+        Self: Sized,
+    {
+        Labelled {
+            parser: self,
+            label,
+        }
+    }
+
+    /// Apply this parser without consuming any input.
+    ///
+    /// The input position is left unchanged whether or not this parser succeeds, which allows
+    /// for overlapping matches.
+    ///
+    /// # Example
+    /// ```
+    /// use aocparse::{text::just, Parser};
+    ///
+    /// // "eightwo" contains both "eight" and "two"
+    /// let parser = just("eight").rewind().then(just("eigh")).then(just("two"));
+    /// assert!(parser.parse_str("eightwo").is_ok());
+    /// ```
+    fn rewind(self) -> Rewind<Self>
+    where
+        Self: Sized,
+    {
+        Rewind { parser: self }
+    }
+
+    /// Apply this parser, but only succeed if the other parser also matches at the same position.
+    ///
+    /// The other parser does not consume any input, and its output is discarded.
+    ///
+    /// # Example
+    /// ```
+    /// use aocparse::{text::{just, number}, Parser};
+    ///
+    /// let starts_with_one = number::<u32>(10).and_is(just("1"));
+    /// assert_eq!(starts_with_one.parse_str("123"), Ok(123));
+    /// assert!(starts_with_one.parse_str("234").is_err());
+    /// ```
+    fn and_is<B, OB>(self, other: B) -> AndIs<Self, B, OB>
+    where
+        Self: Sized,
+        B: Parser<'a, I, OB>,
+    {
+        AndIs {
+            a: self,
+            b: other,
+            __phantom: PhantomData,
+        }
+    }
+
+    /// Succeed only if this parser fails, without consuming any input.
+    ///
+    /// If this parser succeeds, the error reports the input it unexpectedly matched.
+    ///
+    /// # Example
+    /// ```
+    /// use aocparse::{text::{just, number}, Parser};
+    ///
+    /// let unsigned = just("-").not().then(number::<i32>(10)).map(|(_, n)| n);
+    /// assert_eq!(unsigned.parse_str("12"), Ok(12));
+    ///
+    /// let error = unsigned.parse_str("-12").unwrap_err();
+    /// assert_eq!(error.to_string(), "at offset 0: unexpected \"-\"");
+    /// ```
+    fn not(self) -> Not<Self, O>
+    where
+        Self: Sized,
+    {
+        Not {
+            parser: self,
+            __phantom: PhantomData,
+        }
+    }
+
+    /// Memoize the results of this parser, making it a packrat parser.
+    ///
+    /// See [`Memoized`] for details.
+    fn memoized(self) -> Memoized<Self, O>
+    where
+        Self: Sized,
+    {
+        Memoized::new(self)
+    }
+
     /// Make this parser optional.
     fn optional(self) -> Maybe<Self>
     where
@@ -107,59 +784,287 @@ pub trait Parser<'a, I, O>: Clone {
     }
 
     /// Repeat this parser until it fails.
+    ///
+    /// The number of repetitions can be bounded using [`Repeated::at_least`],
+    /// [`Repeated::at_most`] and [`Repeated::exactly`].
+    ///
+    /// # Example
+    /// ```
+    /// use aocparse::{text::just, Parser};
+    ///
+    /// let parser = just("ab").repeated().at_least(1).at_most(2);
+    /// assert_eq!(parser.parse_str("ababab"), Ok(vec![(), ()]));
+    /// assert!(parser.parse_str("ba").is_err());
+    /// ```
     fn repeated(self) -> Repeated<Self, O>
     where
         Self: Sized,
     {
         Repeated {
             parser: self,
+            at_least: 0,
+            at_most: None,
             __phantom: PhantomData,
         }
     }
 
-    /// Repeat this parser a fixed number of times.
+    /// Repeat this parser, with each item separated by the given separator.
     ///
     /// This is useful for parsing a sequence of tokens separated by some delimiter. For example,
-    /// many programming languages use commas to separate tokens.
-    fn delimited_by<D>(self, delimiter: D) -> DelimitedBy<Self, D>
+    /// many programming languages use commas to separate tokens. A separator that is not
+    /// followed by an item is not consumed.
+    ///
+    /// # Example
+    /// ```
+    /// use aocparse::{text::{just, number}, Parser};
+    ///
+    /// let list = number::<u32>(10).separated_by(just(",")).allow_trailing();
+    /// assert_eq!(list.parse_str("1,2,3,"), Ok(vec![1, 2, 3]));
+    /// ```
+    fn separated_by<S, OS>(self, separator: S) -> SeparatedBy<Self, S, OS>
     where
         Self: Sized,
+        S: Parser<'a, I, OS>,
     {
-        DelimitedBy {
+        SeparatedBy {
             item: self,
-            delimiter,
+            separator,
+            at_least: 0,
+            at_most: None,
+            allow_leading: false,
+            allow_trailing: false,
+            __phantom: PhantomData,
         }
     }
 
     /// Fold the output of this parser using the given function.
     ///
     /// This can be used to combine the results from a sequences of parsers, or the result
-    /// of a parser like [Parser::repeated].
-    fn foldl<F>(self, f: F) -> Foldl<Self, F, O>
+    /// of a parser like [Parser::repeated]. The outputs are folded from left to right, and
+    /// parsing fails if there are no outputs to fold.
+    fn foldl<T, F>(self, f: F) -> Foldl<Self, F, T>
     where
-        F: Fn(O, O) -> O,
+        Self: Parser<'a, I, Vec<T>>,
+        F: Fn(T, T) -> T,
     {
         Foldl {
             parser: self,
-            f,
+            f: Arc::new(f),
             __phantom: PhantomData,
         }
     }
 
+    /// Erase the type of this parser, behind a reference-counted pointer.
+    ///
+    /// Chains of combinators produce deeply nested types, which are slow to compile and cannot
+    /// easily be named. A boxed parser has a simple type, so it can be stored in a struct field,
+    /// returned from a function or built up in a loop, and cloning it is cheap. Boxed parsers
+    /// cannot be used on multiple threads; see [`Parser::boxed_sync`].
+    ///
+    /// # Example
+    /// ```
+    /// use aocparse::{boxed::BoxedParser, text::just, Parser};
+    ///
+    /// // build a parser for any of the given words, trying them in order
+    /// let words = ["twenty", "two", "one"];
+    /// let mut parser: BoxedParser<&str, &str> = just(words[0]).to(words[0]).boxed();
+    /// for word in &words[1..] {
+    ///     parser = parser.or(just(word).to(*word)).boxed();
+    /// }
+    /// assert_eq!(parser.parse_str("twenty"), Ok("twenty"));
+    /// assert_eq!(parser.parse_str("one"), Ok("one"));
+    /// ```
+    fn boxed(self) -> BoxedParser<'a, I, O>
+    where
+        Self: Sized + 'a,
+    {
+        BoxedParser {
+            parser: Rc::new(self),
+        }
+    }
+
+    /// Erase the type of this parser, behind an atomically reference-counted pointer.
+    ///
+    /// This is like [`Parser::boxed`], but the boxed parser can be shared between threads, such
+    /// as by [`Parser::parse_parallel`].
+    fn boxed_sync(self) -> SyncBoxedParser<'a, I, O>
+    where
+        Self: Sized + Send + Sync + 'a,
+    {
+        SyncBoxedParser {
+            parser: Arc::new(self),
+        }
+    }
+
     /// Parse the given input.
-    fn parse(&self, input: &mut Input<'a, I>) -> Result<O, ()>;
+    fn parse(&self, input: &mut Input<'a, I>) -> Result<O, Error>;
+
+    /// Parse the given input, failing unless the whole input is consumed.
+    ///
+    /// If input is left unconsumed, the error contains the trailing input.
+    fn parse_complete(&self, input: &mut Input<'a, I>) -> Result<O, Error>
+    where
+        I: Stream,
+    {
+        input.attempt(|input| {
+            let output = self.parse(input)?;
+            end().parse(input).map_err(|e| {
+                let mut e = input.merge_alt(e);
+                // describing the trailing input is costly, so only do it once parsing is over
+                if e.pos == input.pos {
+                    e.found = input.source.rest(input.pos);
+                }
+                e
+            })?;
+            Ok(output)
+        })
+    }
+
+    /// Parse records from the given input one at a time, as an iterator.
+    ///
+    /// Records are parsed lazily, as the iterator is advanced, so large inputs can be processed
+    /// without collecting every record first. By default, records follow each other directly;
+    /// use [`ParseIter::separator`](iter::ParseIter::separator) to expect a separator between
+    /// them. The iterator ends at the end of the input, and stops after producing the first
+    /// error.
+    ///
+    /// # Example
+    /// ```
+    /// use aocparse::{text::{just, number}, Parser};
+    ///
+    /// let depths = number::<u32>(10).parse_iter("199\n200\n208\n").separator(just("\n"));
+    /// assert_eq!(depths.map(Result::unwrap).sum::<u32>(), 607);
+    ///
+    /// let mut depths = number::<u32>(10).parse_iter("199\n2x").separator(just("\n"));
+    /// assert_eq!(depths.next(), Some(Ok(199)));
+    /// assert_eq!(depths.next(), Some(Ok(2)));
+    /// assert_eq!(depths.next().unwrap().unwrap_err().pos(), 5);
+    /// assert_eq!(depths.next(), None);
+    /// ```
+    fn parse_iter<T>(self, input: T) -> ParseIter<'a, Self, Empty<I>, I, O, ()>
+    where
+        Self: Sized,
+        T: Into<Input<'a, I>>,
+    {
+        ParseIter {
+            parser: self,
+            separator: empty(),
+            input: input.into(),
+            started: false,
+            done: false,
+            __phantom: PhantomData,
+        }
+    }
+
+    /// Parse each record of the given string on a pool of threads, where records are separated
+    /// by the given delimiter.
+    ///
+    /// Every record must be consumed in full by this parser. The outputs are returned in the
+    /// order of the records, and if any record fails to parse, the error from the first such
+    /// record is returned, with its position relative to the start of the whole string. Spans,
+    /// as produced by [`Parser::map_with_span`], are also relative to the start of the whole
+    /// string. A delimiter at the end of the string is ignored.
+    ///
+    /// Parsers built with [`recursive`](recursive::recursive), [`pratt`](pratt::pratt),
+    /// [`Parser::memoized`] or a [`Lexer`](lexer::Lexer) share state between clones, so cannot
+    /// be used on multiple threads.
+    ///
+    /// # Panics
+    /// Panics if the delimiter is empty, or if parsing any record panics.
+    ///
+    /// # Example
+    /// ```
+    /// use aocparse::{text::{just, number}, Parser};
+    ///
+    /// let range = number::<u32>(10).then(just("-")).then(number::<u32>(10));
+    /// let ranges = range.map(|((a, _), b)| (a, b));
+    /// assert_eq!(ranges.parse_parallel("2-4\n6-8\n", "\n"), Ok(vec![(2, 4), (6, 8)]));
+    ///
+    /// let error = ranges.parse_parallel("2-4\n6-\n", "\n").unwrap_err();
+    /// assert_eq!(error.pos(), 6);
+    /// assert_eq!(error.found(), Some("\n"));
+    /// ```
+    fn parse_parallel(&self, input: &'a str, delimiter: &str) -> Result<Vec<O>, Error>
+    where
+        Self: Parser<'a, &'a str, O> + Sync,
+        O: Send,
+    {
+        parallel::parse_records(self, input, delimiter)
+    }
 
     /// Parse the given string.
-    fn parse_str(&self, input: &'a str) -> Result<O, ()>
+    ///
+    /// This succeeds even if only a prefix of the string is consumed. See
+    /// [`Parser::parse_str_all`] and [`Parser::parse_prefix`].
+    fn parse_str(&self, input: &'a str) -> Result<O, Error>
     where
         Self: Parser<'a, &'a str, O>,
     {
-        let mut input = Input {
-            pos: 0,
-            source: input,
-            __phantom: PhantomData,
-        };
-        self.parse(&mut input)
+        self.parse(&mut input.into())
+    }
+
+    /// Parse the given string, failing unless the whole string is consumed.
+    ///
+    /// # Example
+    /// ```
+    /// use aocparse::{text::number, Parser};
+    ///
+    /// let integer = number::<u32>(10);
+    /// assert_eq!(integer.parse_str("12ab"), Ok(12));
+    ///
+    /// let error = integer.parse_str_all("12ab").unwrap_err();
+    /// assert_eq!(error.pos(), 2);
+    /// assert_eq!(error.found(), Some("ab"));
+    /// ```
+    fn parse_str_all(&self, input: &'a str) -> Result<O, Error>
+    where
+        Self: Parser<'a, &'a str, O>,
+    {
+        self.parse_complete(&mut input.into())
+    }
+
+    /// Parse a prefix of the given string, returning the output along with the remaining input.
+    ///
+    /// # Example
+    /// ```
+    /// use aocparse::{text::number, Parser};
+    ///
+    /// assert_eq!(number::<u32>(10).parse_prefix("12ab"), Ok((12, "ab")));
+    /// ```
+    fn parse_prefix(&self, input: &'a str) -> Result<(O, &'a str), Error>
+    where
+        Self: Parser<'a, &'a str, O>,
+    {
+        let mut input = Input::from(input);
+        let output = self.parse(&mut input)?;
+        Ok((output, input.source.get(input.pos..).unwrap_or_default()))
+    }
+
+    /// Parse the given bytes.
+    ///
+    /// This succeeds even if only a prefix of the bytes is consumed. See
+    /// [`Parser::parse_bytes_all`].
+    fn parse_bytes(&self, input: &'a [u8]) -> Result<O, Error>
+    where
+        Self: Parser<'a, &'a [u8], O>,
+    {
+        self.parse(&mut input.into())
+    }
+
+    /// Parse the given bytes, failing unless all of the bytes are consumed.
+    ///
+    /// # Example
+    /// ```
+    /// use aocparse::{bytes::byte, Parser};
+    ///
+    /// assert_eq!(byte(b'a').parse_bytes_all(b"a"), Ok(b'a'));
+    /// assert!(byte(b'a').parse_bytes_all(b"ab").is_err());
+    /// ```
+    fn parse_bytes_all(&self, input: &'a [u8]) -> Result<O, Error>
+    where
+        Self: Parser<'a, &'a [u8], O>,
+    {
+        self.parse_complete(&mut input.into())
     }
 }
 
@@ -169,7 +1074,21 @@ where
     P: Parser<'a, I, O>,
     I: Clone,
 {
-    fn parse(&self, input: &mut Input<'a, I>) -> Result<O, ()> {
+    fn parse(&self, input: &mut Input<'a, I>) -> Result<O, Error> {
         self.as_ref().parse(input)
     }
 }
+
+/// An object-safe version of [`Parser`], used to erase the types of parsers.
+pub(crate) trait DynParser<'a, I, O> {
+    fn parse_dyn(&self, input: &mut Input<'a, I>) -> Result<O, Error>;
+}
+
+impl<'a, I, O, P> DynParser<'a, I, O> for P
+where
+    P: Parser<'a, I, O>,
+{
+    fn parse_dyn(&self, input: &mut Input<'a, I>) -> Result<O, Error> {
+        self.parse(input)
+    }
+}
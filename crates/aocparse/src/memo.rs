@@ -0,0 +1,169 @@
+//! Defines memoization of parser results, for packrat parsing.
+
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    rc::Rc,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::{Error, Input, Parser, Stream};
+
+static NEXT_INPUT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Allocate a unique identifier for a new input.
+pub(crate) fn next_input_id() -> usize {
+    NEXT_INPUT_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// A memoized result of a parser at a given position.
+enum Entry<O> {
+    /// The parser is currently being applied at this position. Reaching this entry again means
+    /// that the parser is left-recursive.
+    InProgress { recursed: bool },
+    /// The parser has finished at this position, with its output, end position and the
+    /// diagnostics it recorded.
+    Done(Result<(O, usize, Vec<Error>), Error>),
+}
+
+/// The memoized results of a parser against a single input.
+struct Table<O> {
+    input: usize,
+    entries: HashMap<usize, Entry<O>>,
+}
+
+/// A parser that memoizes the results of its child parser.
+///
+/// Results are keyed by the identity of this parser, shared between its clones, and the position
+/// in the input, so the child is applied at most once at each position of an input. This bounds
+/// the cost of grammars that backtrack heavily through [`Parser::or`].
+///
+/// Memoized parsers also support direct left recursion, by growing a seed result until the
+/// parser stops consuming more input. This allows for rules like `expr = expr "-" term | term`
+/// to be written naturally using [`recursive`](crate::recursive::recursive).
+///
+/// # Example
+/// ```
+/// use aocparse::{recursive::recursive, text::{just, number}, Parser};
+///
+/// let expr = recursive(|expr| {
+///     expr.then(just("-"))
+///         .then(number::<i32>(10))
+///         .map(|((a, _), b)| a - b)
+///         .or(number(10))
+///         .memoized()
+/// });
+/// assert_eq!(expr.parse_str("10-4-3"), Ok(3));
+/// ```
+pub struct Memoized<P, O> {
+    parser: P,
+    table: Rc<RefCell<Table<O>>>,
+}
+
+impl<P, O> Memoized<P, O> {
+    pub(crate) fn new(parser: P) -> Self {
+        Memoized {
+            parser,
+            table: Rc::new(RefCell::new(Table {
+                input: usize::MAX,
+                entries: HashMap::new(),
+            })),
+        }
+    }
+
+    /// Record the given entry at a position of the input.
+    fn insert<I>(&self, input: &Input<'_, I>, pos: usize, entry: Entry<O>) {
+        let mut table = self.table.borrow_mut();
+        if table.input != input.id {
+            table.input = input.id;
+            table.entries.clear();
+        }
+        table.entries.insert(pos, entry);
+    }
+}
+
+impl<P: Clone, O> Clone for Memoized<P, O> {
+    fn clone(&self) -> Self {
+        Memoized {
+            parser: self.parser.clone(),
+            table: self.table.clone(),
+        }
+    }
+}
+
+impl<'a, I, O, P> Parser<'a, I, O> for Memoized<P, O>
+where
+    P: Parser<'a, I, O>,
+    I: Stream,
+    O: Clone,
+{
+    fn parse(&self, input: &mut Input<'a, I>) -> Result<O, Error> {
+        let start = input.pos;
+        {
+            let mut table = self.table.borrow_mut();
+            if table.input == input.id {
+                match table.entries.get_mut(&start) {
+                    Some(Entry::Done(Ok((o, end, diagnostics)))) => {
+                        input.pos = *end;
+                        input.diagnostics.extend(diagnostics.iter().cloned());
+                        return Ok(o.clone());
+                    }
+                    Some(Entry::Done(Err(e))) => return Err(e.clone()),
+                    Some(Entry::InProgress { recursed }) => {
+                        *recursed = true;
+                        return Err(input.error([]));
+                    }
+                    None => {}
+                }
+            }
+        }
+
+        let diagnostics = input.diagnostics.len();
+        self.insert(input, start, Entry::InProgress { recursed: false });
+        let result = input.attempt(|input| self.parser.parse(input));
+        let recursed = matches!(
+            self.table.borrow().entries.get(&start),
+            Some(Entry::InProgress { recursed: true })
+        );
+
+        let result = match result {
+            // grow the seed until the parser stops making progress
+            Ok(mut o) if recursed => loop {
+                let end = input.pos;
+                // the seed's diagnostics are replayed when the parser reaches it again
+                let recorded = input.diagnostics.split_off(diagnostics);
+                let seed = Entry::Done(Ok((o.clone(), end, recorded.clone())));
+                self.insert(input, start, seed);
+                input.pos = start;
+                match input.attempt(|input| self.parser.parse(input)) {
+                    Ok(grown) if input.pos > end => o = grown,
+                    Ok(_) => {
+                        input.pos = end;
+                        input.diagnostics.truncate(diagnostics);
+                        input.diagnostics.extend(recorded);
+                        break Ok(o);
+                    }
+                    Err(e) if e.committed => break Err(e),
+                    Err(e) => {
+                        input.recover(e);
+                        input.pos = end;
+                        input.diagnostics.truncate(diagnostics);
+                        input.diagnostics.extend(recorded);
+                        break Ok(o);
+                    }
+                }
+            },
+            result => result,
+        };
+
+        let entry = match &result {
+            Ok(o) => {
+                let recorded = input.diagnostics.get(diagnostics..).unwrap_or_default();
+                Ok((o.clone(), input.pos, recorded.to_vec()))
+            }
+            Err(e) => Err(e.clone()),
+        };
+        self.insert(input, start, Entry::Done(entry));
+        result
+    }
+}
@@ -0,0 +1,80 @@
+//! Defines parsing of independent records on multiple threads.
+
+use std::{num::NonZeroUsize, panic, thread};
+
+use crate::{Error, Input, Parser, Source};
+
+/// Split the input into records, along with the offset at which each one starts.
+///
+/// A delimiter at the very end of the input does not start another record.
+fn split<'a>(input: &'a str, delimiter: &str) -> Vec<(usize, &'a str)> {
+    assert!(!delimiter.is_empty(), "record delimiter must not be empty");
+    let input = input.strip_suffix(delimiter).unwrap_or(input);
+    if input.is_empty() {
+        return Vec::new();
+    }
+    let mut offset = 0;
+    input
+        .split(delimiter)
+        .map(|record| {
+            let start = offset;
+            offset += record.len() + delimiter.len();
+            (start, record)
+        })
+        .collect()
+}
+
+/// Parse each of the given records in full, stopping at the first error.
+fn parse_chunk<'a, P, O>(
+    parser: &P,
+    input: &'a str,
+    records: &[(usize, &'a str)],
+) -> Result<Vec<O>, Error>
+where
+    P: Parser<'a, &'a str, O>,
+{
+    records
+        .iter()
+        .map(|&(start, record)| {
+            let mut record = Input::from(record);
+            record.offset = start;
+            parser.parse_complete(&mut record).map_err(|mut e| {
+                e.pos += start;
+                // the end of a record is usually a delimiter in the whole input
+                if e.found.is_none() {
+                    e.found = input.found(e.pos);
+                }
+                e
+            })
+        })
+        .collect()
+}
+
+/// See [`Parser::parse_parallel`].
+pub(crate) fn parse_records<'a, P, O>(
+    parser: &P,
+    input: &'a str,
+    delimiter: &str,
+) -> Result<Vec<O>, Error>
+where
+    P: Parser<'a, &'a str, O> + Sync,
+    O: Send,
+{
+    let records = split(input, delimiter);
+    let threads = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    let chunk_size = records.len().div_ceil(threads).max(1);
+    thread::scope(|scope| {
+        let workers: Vec<_> = records
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || parse_chunk(parser, input, chunk)))
+            .collect();
+        let mut outputs = Vec::with_capacity(records.len());
+        for worker in workers {
+            let chunk = worker
+                .join()
+                .unwrap_or_else(|payload| panic::resume_unwind(payload))?;
+            outputs.extend(chunk);
+        }
+        Ok(outputs)
+    })
+}
@@ -0,0 +1,251 @@
+//! Defines a builder for operator-precedence expression parsers.
+
+use std::rc::Rc;
+
+use crate::{DynParser, Error, Input, Parser, Stream};
+
+/// The associativity of an infix operator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Assoc {
+    /// `a - b - c` is parsed as `(a - b) - c`.
+    Left,
+    /// `a ^ b ^ c` is parsed as `a ^ (b ^ c)`.
+    Right,
+}
+
+/// A unary operator, applied before or after its operand.
+struct Unary<'a, I, O> {
+    parser: Rc<dyn DynParser<'a, I, ()> + 'a>,
+    power: u32,
+    fold: Rc<dyn Fn(O) -> O + 'a>,
+}
+
+impl<'a, I, O> Clone for Unary<'a, I, O> {
+    fn clone(&self) -> Self {
+        Unary {
+            parser: self.parser.clone(),
+            power: self.power,
+            fold: self.fold.clone(),
+        }
+    }
+}
+
+/// A binary operator, applied between its operands.
+struct Infix<'a, I, O> {
+    parser: Rc<dyn DynParser<'a, I, ()> + 'a>,
+    left_power: u32,
+    right_power: u32,
+    fold: Rc<dyn Fn(O, O) -> O + 'a>,
+}
+
+impl<'a, I, O> Clone for Infix<'a, I, O> {
+    fn clone(&self) -> Self {
+        Infix {
+            parser: self.parser.clone(),
+            left_power: self.left_power,
+            right_power: self.right_power,
+            fold: self.fold.clone(),
+        }
+    }
+}
+
+/// See [`pratt`].
+pub struct Pratt<'a, I, O, A> {
+    atom: A,
+    prefix: Vec<Unary<'a, I, O>>,
+    infix: Vec<Infix<'a, I, O>>,
+    postfix: Vec<Unary<'a, I, O>>,
+}
+
+impl<'a, I, O, A: Clone> Clone for Pratt<'a, I, O, A> {
+    fn clone(&self) -> Self {
+        Pratt {
+            atom: self.atom.clone(),
+            prefix: self.prefix.clone(),
+            infix: self.infix.clone(),
+            postfix: self.postfix.clone(),
+        }
+    }
+}
+
+impl<'a, I, O, A> Pratt<'a, I, O, A>
+where
+    A: Parser<'a, I, O>,
+    I: Stream,
+{
+    /// Add a prefix operator with the given binding power.
+    ///
+    /// The fold function is called with the operand to produce the output.
+    pub fn prefix<P, OP, F>(mut self, op: P, power: u16, fold: F) -> Self
+    where
+        P: Parser<'a, I, OP> + 'a,
+        OP: 'a,
+        F: Fn(O) -> O + 'a,
+    {
+        self.prefix.push(Unary {
+            parser: Rc::new(op.ignored()),
+            power: 2 * power as u32,
+            fold: Rc::new(fold),
+        });
+        self
+    }
+
+    /// Add an infix operator with the given associativity and binding power.
+    ///
+    /// The fold function is called with the left and right operands to produce the output.
+    pub fn infix<P, OP, F>(mut self, op: P, assoc: Assoc, power: u16, fold: F) -> Self
+    where
+        P: Parser<'a, I, OP> + 'a,
+        OP: 'a,
+        F: Fn(O, O) -> O + 'a,
+    {
+        let power = 2 * power as u32;
+        let (left_power, right_power) = match assoc {
+            Assoc::Left => (power, power + 1),
+            Assoc::Right => (power + 1, power),
+        };
+        self.infix.push(Infix {
+            parser: Rc::new(op.ignored()),
+            left_power,
+            right_power,
+            fold: Rc::new(fold),
+        });
+        self
+    }
+
+    /// Add a postfix operator with the given binding power.
+    ///
+    /// The fold function is called with the operand to produce the output.
+    pub fn postfix<P, OP, F>(mut self, op: P, power: u16, fold: F) -> Self
+    where
+        P: Parser<'a, I, OP> + 'a,
+        OP: 'a,
+        F: Fn(O) -> O + 'a,
+    {
+        self.postfix.push(Unary {
+            parser: Rc::new(op.ignored()),
+            power: 2 * power as u32,
+            fold: Rc::new(fold),
+        });
+        self
+    }
+
+    /// Parse an operand, along with any prefix operators applied to it.
+    fn parse_operand(&self, input: &mut Input<'a, I>) -> Result<O, Error> {
+        let mut error: Option<Error> = None;
+        for op in &self.prefix {
+            match input.attempt(|input| op.parser.parse_dyn(input)) {
+                Ok(()) => {
+                    let operand = self.parse_expr(input, op.power)?;
+                    return Ok((op.fold)(operand));
+                }
+                Err(e) if e.committed => return Err(e),
+                Err(e) => error = Some(merge(error, e)),
+            }
+        }
+        match self.atom.parse(input) {
+            Ok(o) => {
+                if let Some(error) = error {
+                    input.recover(error);
+                }
+                Ok(o)
+            }
+            Err(e) => Err(merge(error, e)),
+        }
+    }
+
+    /// Parse an expression whose operators bind at least as tightly as `min_power`.
+    fn parse_expr(&self, input: &mut Input<'a, I>, min_power: u32) -> Result<O, Error> {
+        let mut lhs = self.parse_operand(input)?;
+        'ops: loop {
+            for op in &self.postfix {
+                let marker = input.save();
+                let result = op.parser.parse_dyn(input);
+                if result.is_err() || op.power < min_power {
+                    input.rewind(marker);
+                }
+                input.release(marker);
+                match result {
+                    Ok(()) if op.power < min_power => break 'ops,
+                    Ok(()) => {
+                        lhs = (op.fold)(lhs);
+                        continue 'ops;
+                    }
+                    Err(e) if e.committed => return Err(e),
+                    Err(e) => input.recover(e),
+                }
+            }
+            for op in &self.infix {
+                let marker = input.save();
+                let result = op.parser.parse_dyn(input);
+                if result.is_err() || op.left_power < min_power {
+                    input.rewind(marker);
+                }
+                input.release(marker);
+                match result {
+                    Ok(()) if op.left_power < min_power => break 'ops,
+                    Ok(()) => {
+                        let rhs = self.parse_expr(input, op.right_power)?;
+                        lhs = (op.fold)(lhs, rhs);
+                        continue 'ops;
+                    }
+                    Err(e) if e.committed => return Err(e),
+                    Err(e) => input.recover(e),
+                }
+            }
+            break;
+        }
+        Ok(lhs)
+    }
+}
+
+impl<'a, I, O, A> Parser<'a, I, O> for Pratt<'a, I, O, A>
+where
+    A: Parser<'a, I, O>,
+    I: Stream,
+{
+    fn parse(&self, input: &mut Input<'a, I>) -> Result<O, Error> {
+        input.attempt(|input| self.parse_expr(input, 0))
+    }
+}
+
+/// Merge an error with a previous error, if there was one.
+fn merge(error: Option<Error>, e: Error) -> Error {
+    match error {
+        Some(error) => error.merge(e),
+        None => e,
+    }
+}
+
+/// Create an operator-precedence parser for expressions built from the given atom.
+///
+/// Operators are added using [`Pratt::prefix`], [`Pratt::infix`] and [`Pratt::postfix`]. Each
+/// operator has a binding power, where operators with a higher binding power bind more tightly,
+/// and a fold function used to produce the output. Operators are tried in the order they were
+/// added.
+///
+/// # Example
+/// ```
+/// use aocparse::{
+///     pratt::{pratt, Assoc},
+///     text::{just, number},
+///     Parser,
+/// };
+///
+/// let expr = pratt(number::<i64>(10))
+///     .prefix(just("-"), 3, |a| -a)
+///     .infix(just("+"), Assoc::Left, 1, |a, b| a + b)
+///     .infix(just("*"), Assoc::Left, 2, |a, b| a * b);
+/// assert_eq!(expr.parse_str("1+2*-3"), Ok(-5));
+/// ```
+pub fn pratt<'a, I, O, A>(atom: A) -> Pratt<'a, I, O, A>
+where
+    A: Parser<'a, I, O>,
+{
+    Pratt {
+        atom,
+        prefix: Vec::new(),
+        infix: Vec::new(),
+        postfix: Vec::new(),
+    }
+}
@@ -1,45 +1,109 @@
 //! Defines core primitive parsers.
 
-use std::marker::PhantomData;
+use std::{fmt::Display, marker::PhantomData, sync::Arc};
 
-use crate::{Input, Parser};
+use crate::{span::Spanned, Error, Expected, Input, Parser, Source, Stream};
 
 /// A parser that allows for the mapping of its child output to another type.
-#[derive(Copy)]
 pub struct Map<P, F, O> {
-    pub parser: P,
-    pub f: F,
-    pub __phantom: PhantomData<O>,
+    pub(crate) parser: P,
+    pub(crate) f: Arc<F>,
+    pub(crate) __phantom: PhantomData<fn() -> O>,
 }
 
-impl<P: Clone, F: Clone + Copy, O> Clone for Map<P, F, O> {
+impl<P: Clone, F, O> Clone for Map<P, F, O> {
     fn clone(&self) -> Self {
         Map {
             parser: self.parser.clone(),
-            f: self.f,
+            f: Arc::clone(&self.f),
             __phantom: PhantomData,
         }
     }
 }
 
-impl<'a, P, AI, AO, BO, F: Clone + Copy> Parser<'a, AI, BO> for Map<P, F, AO>
+impl<'a, P, AI, AO, BO, F> Parser<'a, AI, BO> for Map<P, F, AO>
 where
     P: Parser<'a, AI, AO> + Sized,
     F: Fn(AO) -> BO,
 {
-    fn parse(&self, input: &mut Input<'a, AI>) -> Result<BO, ()> {
+    fn parse(&self, input: &mut Input<'a, AI>) -> Result<BO, Error> {
         self.parser.parse(input).map(|o| (self.f)(o))
     }
 }
 
+/// See [`Parser::map_with_span`].
+pub struct MapWithSpan<P, F, O> {
+    pub(crate) parser: P,
+    pub(crate) f: Arc<F>,
+    pub(crate) __phantom: PhantomData<fn() -> O>,
+}
+
+impl<P: Clone, F, O> Clone for MapWithSpan<P, F, O> {
+    fn clone(&self) -> Self {
+        MapWithSpan {
+            parser: self.parser.clone(),
+            f: Arc::clone(&self.f),
+            __phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, P, I, AO, BO, F> Parser<'a, I, BO> for MapWithSpan<P, F, AO>
+where
+    P: Parser<'a, I, AO> + Sized,
+    I: Stream,
+    F: Fn(AO, I::Span) -> BO,
+{
+    fn parse(&self, input: &mut Input<'a, I>) -> Result<BO, Error> {
+        let start = input.pos;
+        let output = self.parser.parse(input)?;
+        let span = input
+            .source
+            .span(input.offset + start, input.offset + input.pos);
+        Ok((self.f)(output, span))
+    }
+}
+
+/// A parser that wraps its child output in a [`Spanned`].
+pub type WithSpan<P, O, S> = MapWithSpan<P, fn(O, S) -> Spanned<O, S>, O>;
+
+/// See [`Parser::to_slice`].
+pub struct ToSlice<P, O> {
+    pub(crate) parser: P,
+    pub(crate) __phantom: PhantomData<fn() -> O>,
+}
+
+impl<P: Clone, O> Clone for ToSlice<P, O> {
+    fn clone(&self) -> Self {
+        ToSlice {
+            parser: self.parser.clone(),
+            __phantom: PhantomData,
+        }
+    }
+}
+
+impl<P: Copy, O> Copy for ToSlice<P, O> {}
+
+impl<'a, P, I, O> Parser<'a, I, I::Slice> for ToSlice<P, O>
+where
+    P: Parser<'a, I, O>,
+    I: Stream,
+{
+    fn parse(&self, input: &mut Input<'a, I>) -> Result<I::Slice, Error> {
+        let start = input.pos;
+        self.parser.parse(input)?;
+        Ok(input.source.slice(start, input.pos))
+    }
+}
+
 /// A parser that maps its child output to `()`.
 pub type Ignored<P, O> = To<P, O, ()>;
 
 /// A parser that maps its child output to a constant value.
 pub struct To<P, O, T> {
-    pub parser: P,
-    pub value: T,
-    pub __phantom: PhantomData<O>,
+    pub(crate) parser: P,
+    pub(crate) value: T,
+    pub(crate) __phantom: PhantomData<fn() -> O>,
 }
 
 impl<P, O, T> Clone for To<P, O, T>
@@ -61,37 +125,187 @@ where
     P: Parser<'a, I, O> + Sized,
     T: Clone,
 {
-    fn parse(&self, input: &mut Input<'a, I>) -> Result<T, ()> {
+    fn parse(&self, input: &mut Input<'a, I>) -> Result<T, Error> {
         self.parser.parse(input).map(|_| self.value.clone())
     }
 }
 
-/// A parser that allows for the filtering of its child output.
-struct Filter<P, F> {
-    parser: P,
-    f: F,
+/// See [`Parser::filter`].
+pub struct Filter<P, F> {
+    pub(crate) parser: P,
+    pub(crate) f: Arc<F>,
 }
 
-impl<P: Clone, F: Clone + Copy> Clone for Filter<P, F> {
+impl<P: Clone, F> Clone for Filter<P, F> {
     fn clone(&self) -> Self {
         Filter {
             parser: self.parser.clone(),
-            f: self.f,
+            f: Arc::clone(&self.f),
         }
     }
 }
 
-impl<'a, P, I, O, F: Clone + Copy> Parser<'a, I, O> for Filter<P, F>
+impl<'a, P, I, O, F> Parser<'a, I, O> for Filter<P, F>
 where
     P: Parser<'a, I, O> + Sized,
+    I: Stream,
     F: Fn(&O) -> bool,
 {
-    fn parse(&self, input: &mut Input<'a, I>) -> Result<O, ()> {
-        let result = self.parser.parse(input)?;
-        if (self.f)(&result) {
-            Ok(result)
-        } else {
-            Err(())
+    fn parse(&self, input: &mut Input<'a, I>) -> Result<O, Error> {
+        let start = input.pos;
+        input.attempt(|input| {
+            let result = self.parser.parse(input)?;
+            if (self.f)(&result) {
+                Ok(result)
+            } else {
+                Err(Error::custom(
+                    start,
+                    "value did not satisfy filter",
+                    input.source.found(start),
+                ))
+            }
+        })
+    }
+}
+
+/// See [`Parser::try_map`].
+pub struct TryMap<P, F, O> {
+    pub(crate) parser: P,
+    pub(crate) f: Arc<F>,
+    pub(crate) __phantom: PhantomData<fn() -> O>,
+}
+
+impl<P: Clone, F, O> Clone for TryMap<P, F, O> {
+    fn clone(&self) -> Self {
+        TryMap {
+            parser: self.parser.clone(),
+            f: Arc::clone(&self.f),
+            __phantom: PhantomData,
         }
     }
 }
+
+impl<'a, P, I, AO, BO, E, F> Parser<'a, I, BO> for TryMap<P, F, AO>
+where
+    P: Parser<'a, I, AO> + Sized,
+    I: Stream,
+    E: Display,
+    F: Fn(AO) -> Result<BO, E>,
+{
+    fn parse(&self, input: &mut Input<'a, I>) -> Result<BO, Error> {
+        let start = input.pos;
+        input.attempt(|input| {
+            let output = self.parser.parse(input)?;
+            (self.f)(output).map_err(|e| Error::custom(start, e, input.source.found(start)))
+        })
+    }
+}
+
+/// See [`Parser::validate`].
+pub struct Validate<P, F> {
+    pub(crate) parser: P,
+    pub(crate) f: Arc<F>,
+}
+
+impl<P: Clone, F> Clone for Validate<P, F> {
+    fn clone(&self) -> Self {
+        Validate {
+            parser: self.parser.clone(),
+            f: Arc::clone(&self.f),
+        }
+    }
+}
+
+impl<'a, P, I, O, E, F> Parser<'a, I, O> for Validate<P, F>
+where
+    P: Parser<'a, I, O> + Sized,
+    I: Source,
+    E: Display,
+    F: Fn(&O) -> Result<(), E>,
+{
+    fn parse(&self, input: &mut Input<'a, I>) -> Result<O, Error> {
+        let start = input.pos;
+        let output = self.parser.parse(input)?;
+        if let Err(e) = (self.f)(&output) {
+            let error = Error::custom(start, e, input.source.found(start));
+            input.diagnostics.push(error);
+        }
+        Ok(output)
+    }
+}
+
+/// See [`end`].
+pub struct End<I> {
+    pub(crate) __phantom: PhantomData<fn() -> I>,
+}
+
+impl<I> Clone for End<I> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<I> Copy for End<I> {}
+
+impl<'a, I: Source> Parser<'a, I, ()> for End<I> {
+    fn parse(&self, input: &mut Input<'a, I>) -> Result<(), Error> {
+        match input.source.found(input.pos) {
+            None => Ok(()),
+            found => Err(Error::new(input.pos, [Expected::End], found)),
+        }
+    }
+}
+
+/// A parser that only succeeds at the end of the input.
+///
+/// When it fails, the error contains the next item of the input, as for other primitives.
+/// [`Parser::parse_complete`] and [`Parser::parse_str_all`] report the whole trailing input
+/// instead.
+///
+/// # Example
+/// ```
+/// use aocparse::{primitive::end, text::number, Parser};
+///
+/// let parser = number::<u32>(10).then(end());
+/// assert!(parser.parse_str("123").is_ok());
+/// assert!(parser.parse_str("123abc").is_err());
+/// ```
+pub fn end<I>() -> End<I> {
+    End {
+        __phantom: PhantomData,
+    }
+}
+
+/// See [`empty`].
+pub struct Empty<I> {
+    pub(crate) __phantom: PhantomData<fn() -> I>,
+}
+
+impl<I> Clone for Empty<I> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<I> Copy for Empty<I> {}
+
+impl<'a, I> Parser<'a, I, ()> for Empty<I> {
+    fn parse(&self, _: &mut Input<'a, I>) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// A parser that matches no input and always succeeds.
+///
+/// # Example
+/// ```
+/// use aocparse::{primitive::empty, text::just, Parser};
+///
+/// let parser = just("a").or(empty());
+/// assert_eq!(parser.parse_prefix("b"), Ok(((), "b")));
+/// ```
+pub fn empty<I>() -> Empty<I> {
+    Empty {
+        __phantom: PhantomData,
+    }
+}
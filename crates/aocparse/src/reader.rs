@@ -0,0 +1,178 @@
+//! Defines a stream that reads text incrementally from a [`BufRead`].
+
+use std::{
+    cell::RefCell,
+    io::{self, BufRead},
+};
+
+use crate::{span::Span, Source, Stream};
+
+/// A stream of text read on demand from a [`BufRead`], such as a buffered file.
+///
+/// Text is read a line at a time as parsers reach it, and is only kept in memory from the
+/// oldest position that parsers may still backtrack to. Once a parser returns to the top level,
+/// having released every position it saved, the text before the position it started at is
+/// dropped. Parsing one record at a time, for example with
+/// [`Parser::parse_iter`](crate::Parser::parse_iter), therefore only keeps around one or two
+/// records, however large the input is. A parser that keeps a position saved across the whole
+/// input, such as a top-level [`Parser::repeated`](crate::Parser::repeated), still buffers the
+/// whole input.
+///
+/// Positions are byte offsets from the start of the input, and slices are owned `String`s, so
+/// text is parsed with the primitives in [`text::generic`](crate::text::generic). The numeric
+/// parsers in [`text`](crate::text) only work with string slices, so numbers are parsed by
+/// converting the digits matched by these primitives. Reading stops at the first I/O error,
+/// which can be retrieved with [`Reader::take_error`].
+///
+/// # Example
+/// ```
+/// use std::io::Cursor;
+///
+/// use aocparse::{reader::Reader, text::generic::{just, take_while}, Input, Parser};
+///
+/// let line = take_while(|c: char| c.is_ascii_digit())
+///     .try_map(|digits: String| digits.parse::<u32>())
+///     .then(just("\n"))
+///     .map(|(n, _)| n);
+///
+/// let mut input = Input::from(Reader::new(Cursor::new("12\n34\n")));
+/// assert_eq!(line.parse(&mut input), Ok(12));
+/// assert_eq!(line.parse(&mut input), Ok(34));
+/// assert_eq!(input.stream().buffered(), 3);
+/// ```
+pub struct Reader<R> {
+    state: RefCell<State<R>>,
+}
+
+/// The mutable state of a [`Reader`].
+struct State<R> {
+    reader: R,
+    /// The text read so far, from `offset` onwards.
+    buffer: String,
+    /// The position of the start of the buffer.
+    offset: usize,
+    /// The positions that parsers may backtrack to, oldest first.
+    saved: Vec<usize>,
+    /// The position before which text is no longer needed.
+    floor: usize,
+    /// Whether the end of the input, or an error, has been reached.
+    done: bool,
+    error: Option<io::Error>,
+}
+
+impl<R: BufRead> State<R> {
+    /// Read lines until the buffer extends past the given position, returning whether it does.
+    fn fill(&mut self, pos: usize) -> bool {
+        while self.offset + self.buffer.len() <= pos && !self.done {
+            self.trim();
+            match self.reader.read_line(&mut self.buffer) {
+                Ok(0) => self.done = true,
+                Ok(_) => {}
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => {
+                    self.error = Some(e);
+                    self.done = true;
+                }
+            }
+        }
+        self.offset + self.buffer.len() > pos
+    }
+
+    /// Drop the text before the floor.
+    fn trim(&mut self) {
+        let len = self
+            .floor
+            .saturating_sub(self.offset)
+            .min(self.buffer.len());
+        if len > 0 && self.buffer.is_char_boundary(len) {
+            self.buffer.drain(..len);
+            self.offset += len;
+        }
+    }
+
+    /// Get the buffered text from the given position, if it is still buffered.
+    fn get(&self, pos: usize) -> Option<&str> {
+        self.buffer.get(pos.checked_sub(self.offset)?..)
+    }
+}
+
+impl<R: BufRead> Reader<R> {
+    /// Create a stream reading from the given reader.
+    pub fn new(reader: R) -> Self {
+        Reader {
+            state: RefCell::new(State {
+                reader,
+                buffer: String::new(),
+                offset: 0,
+                saved: Vec::new(),
+                floor: 0,
+                done: false,
+                error: None,
+            }),
+        }
+    }
+
+    /// The number of bytes of text currently held in memory.
+    pub fn buffered(&self) -> usize {
+        self.state.borrow().buffer.len()
+    }
+
+    /// Take the I/O error that stopped reading, if there was one.
+    ///
+    /// As the stream ends at the first error, a parser that fails at the end of the input
+    /// should check for an error here.
+    pub fn take_error(&self) -> Option<io::Error> {
+        self.state.borrow_mut().error.take()
+    }
+}
+
+impl<R: BufRead> Source for Reader<R> {
+    fn found(&self, pos: usize) -> Option<String> {
+        self.next(pos).map(|(c, _)| c.to_string())
+    }
+}
+
+impl<R: BufRead> Stream for Reader<R> {
+    type Item = char;
+    type Slice = String;
+    type Span = Span;
+
+    fn next(&self, pos: usize) -> Option<(char, usize)> {
+        let mut state = self.state.borrow_mut();
+        if !state.fill(pos) {
+            return None;
+        }
+        let c = state.get(pos)?.chars().next()?;
+        Some((c, pos + c.len_utf8()))
+    }
+
+    fn slice(&self, start: usize, end: usize) -> String {
+        let mut state = self.state.borrow_mut();
+        if end > start {
+            state.fill(end - 1);
+        }
+        let len = end.saturating_sub(start);
+        state
+            .get(start)
+            .and_then(|rest| rest.get(..len))
+            .map(String::from)
+            .unwrap_or_default()
+    }
+
+    fn span(&self, start: usize, end: usize) -> Span {
+        Span::new(start, end)
+    }
+
+    fn save(&self, pos: usize) {
+        self.state.borrow_mut().saved.push(pos);
+    }
+
+    fn release(&self, pos: usize) {
+        let mut state = self.state.borrow_mut();
+        state.saved.pop();
+        if state.saved.is_empty() {
+            state.floor = state.floor.max(pos);
+            state.trim();
+        }
+    }
+}
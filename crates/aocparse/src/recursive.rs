@@ -0,0 +1,84 @@
+//! Defines parsers that can refer to themselves.
+
+use std::{
+    cell::OnceCell,
+    rc::{Rc, Weak},
+};
+
+use crate::{DynParser, Error, Input, Parser};
+
+type Definition<'a, I, O> = OnceCell<Box<dyn DynParser<'a, I, O> + 'a>>;
+
+/// A handle to the definition of a recursive parser.
+///
+/// The handle passed into [`recursive`] does not own the definition, otherwise the parser
+/// would hold a reference to itself and never be dropped.
+enum Handle<'a, I, O> {
+    Owned(Rc<Definition<'a, I, O>>),
+    Unowned(Weak<Definition<'a, I, O>>),
+}
+
+/// See [`recursive`].
+pub struct Recursive<'a, I, O> {
+    handle: Handle<'a, I, O>,
+}
+
+impl<'a, I, O> Clone for Recursive<'a, I, O> {
+    fn clone(&self) -> Self {
+        Recursive {
+            handle: match &self.handle {
+                Handle::Owned(rc) => Handle::Owned(rc.clone()),
+                Handle::Unowned(weak) => Handle::Unowned(weak.clone()),
+            },
+        }
+    }
+}
+
+impl<'a, I, O> Parser<'a, I, O> for Recursive<'a, I, O> {
+    fn parse(&self, input: &mut Input<'a, I>) -> Result<O, Error> {
+        let definition = match &self.handle {
+            Handle::Owned(rc) => rc.clone(),
+            Handle::Unowned(weak) => weak
+                .upgrade()
+                .expect("recursive parser used after it was dropped"),
+        };
+        definition
+            .get()
+            .expect("recursive parser used before it was defined")
+            .parse_dyn(input)
+    }
+}
+
+/// Create a parser that can refer to itself.
+///
+/// The given function is passed a handle to the parser being defined, which can be cloned and
+/// used anywhere within the definition. This allows for nested structures, such as lists of lists
+/// or parenthesised expressions, to be parsed directly.
+///
+/// # Example
+/// ```
+/// use aocparse::{recursive::recursive, text::just, Parser};
+///
+/// // counts the depth of nested parentheses
+/// let depth = recursive(|depth| {
+///     just("(")
+///         .then(depth.optional())
+///         .then(just(")"))
+///         .map(|((_, inner), _)| inner.unwrap_or(0) + 1)
+/// });
+/// assert_eq!(depth.parse_str("((()))"), Ok(3));
+/// ```
+pub fn recursive<'a, I, O, P, F>(f: F) -> Recursive<'a, I, O>
+where
+    P: Parser<'a, I, O> + 'a,
+    F: FnOnce(Recursive<'a, I, O>) -> P,
+{
+    let definition = Rc::new(OnceCell::new());
+    let parser = f(Recursive {
+        handle: Handle::Unowned(Rc::downgrade(&definition)),
+    });
+    let _ = definition.set(Box::new(parser) as Box<dyn DynParser<'a, I, O> + 'a>);
+    Recursive {
+        handle: Handle::Owned(definition),
+    }
+}
@@ -0,0 +1,136 @@
+//! Defines human-readable reports for parse errors.
+
+use std::fmt;
+
+use crate::{span, Error, Input};
+
+const RESET: &str = "\x1b[0m";
+const BOLD_RED: &str = "\x1b[1;31m";
+const BOLD_BLUE: &str = "\x1b[1;34m";
+
+/// A human-readable report of an [`Error`] against the source it was produced from.
+///
+/// The report shows the line and column of the error, the offending line with a caret under
+/// the failing position, and the expected-vs-found message. Reports are plain text by default,
+/// and can be printed with ANSI colors using [`Report::colored`].
+///
+/// # Example
+/// ```
+/// use aocparse::{text::just, Parser};
+///
+/// let source = "Game 1\nGame x";
+/// let parser = just("Game 1\nGame ").then(just("2"));
+/// let error = parser.parse_str(source).unwrap_err();
+/// let report = error.report(source).to_string();
+/// let lines: Vec<_> = report.lines().collect();
+/// assert_eq!(
+///     lines,
+///     [
+///         "error: expected `just(\"2\")`, found \"x\"",
+///         " --> 2:6",
+///         "  |",
+///         "2 | Game x",
+///         "  |      ^",
+///     ]
+/// );
+/// ```
+pub struct Report<'e, 's> {
+    error: &'e Error,
+    source: &'s str,
+    colored: bool,
+}
+
+impl<'e, 's> Report<'e, 's> {
+    /// Create a new plain text report of the error against the given source.
+    pub fn new(error: &'e Error, source: &'s str) -> Self {
+        Report {
+            error,
+            source,
+            colored: false,
+        }
+    }
+
+    /// Set whether the report is printed using ANSI colors.
+    pub fn colored(mut self, colored: bool) -> Self {
+        self.colored = colored;
+        self
+    }
+
+    /// The one-based line and column of the error.
+    ///
+    /// Columns are counted in characters, not bytes.
+    pub fn line_col(&self) -> (usize, usize) {
+        span::line_col(self.source, self.error.pos)
+    }
+
+    /// Find the one-based line number of the error, and the byte offset at which that line starts.
+    fn line_start(&self) -> (usize, usize) {
+        let pos = self.clamped_pos();
+        let before = &self.source[..pos];
+        let line = before.matches('\n').count() + 1;
+        let start = before.rfind('\n').map_or(0, |i| i + 1);
+        (line, start)
+    }
+
+    /// The error position, clamped to the source and moved back onto a character boundary.
+    fn clamped_pos(&self) -> usize {
+        let mut pos = self.error.pos.min(self.source.len());
+        while !self.source.is_char_boundary(pos) {
+            pos -= 1;
+        }
+        pos
+    }
+
+    fn paint(&self, color: &'static str) -> &'static str {
+        if self.colored {
+            color
+        } else {
+            ""
+        }
+    }
+}
+
+impl fmt::Display for Report<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (line, col) = self.line_col();
+        let (_, start) = self.line_start();
+        let text = self.source[start..]
+            .split('\n')
+            .next()
+            .unwrap_or_default()
+            .trim_end_matches('\r');
+        let gutter = " ".repeat(line.to_string().len());
+
+        let (red, blue, reset) = (
+            self.paint(BOLD_RED),
+            self.paint(BOLD_BLUE),
+            self.paint(RESET),
+        );
+
+        writeln!(f, "{red}error{reset}: {}", Message(self.error))?;
+        writeln!(f, "{gutter}{blue}-->{reset} {line}:{col}")?;
+        writeln!(f, "{gutter} {blue}|{reset}")?;
+        writeln!(f, "{blue}{line} |{reset} {text}")?;
+        writeln!(
+            f,
+            "{gutter} {blue}|{reset} {}{red}^{reset}",
+            " ".repeat(col - 1)
+        )
+    }
+}
+
+/// Displays the message of an error without its position.
+struct Message<'e>(&'e Error);
+
+impl fmt::Display for Message<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_message(f)
+    }
+}
+
+impl<'a> Input<'a, &'a str> {
+    /// Create a human-readable report of an error produced while parsing this input.
+    pub fn report<'e>(&self, error: &'e Error) -> Report<'e, 'a> {
+        Report::new(error, self.source)
+    }
+}
@@ -0,0 +1,141 @@
+//! Defines spans, which record the region of the input that a value came from.
+
+use std::{fmt, ops::Range};
+
+/// A region of the input, from a start position up to, but not including, an end position.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Span {
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+}
+
+impl Span {
+    /// Create a new span between the given positions.
+    pub fn new(start: usize, end: usize) -> Self {
+        Span { start, end }
+    }
+
+    /// The position at which the span starts.
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// The position at which the span ends.
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    /// The length of the span.
+    pub fn len(&self) -> usize {
+        self.end.saturating_sub(self.start)
+    }
+
+    /// Whether the span is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The span as a range, which can be used to index the input.
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    /// The one-based line and column at which the span starts in the given source.
+    ///
+    /// Columns are counted in characters, not bytes.
+    ///
+    /// # Example
+    /// ```
+    /// use aocparse::span::Span;
+    ///
+    /// let source = "467..114\n...*..";
+    /// assert_eq!(Span::new(5, 8).start_line_col(source), (1, 6));
+    /// assert_eq!(Span::new(12, 13).start_line_col(source), (2, 4));
+    /// ```
+    pub fn start_line_col(&self, source: &str) -> (usize, usize) {
+        line_col(source, self.start)
+    }
+
+    /// The one-based line and column at which the span ends in the given source, which is just
+    /// past its last character.
+    ///
+    /// Columns are counted in characters, not bytes.
+    pub fn end_line_col(&self, source: &str) -> (usize, usize) {
+        line_col(source, self.end)
+    }
+}
+
+/// Find the one-based line and column of the given byte offset in the source.
+///
+/// Offsets past the end of the source are clamped to it, and offsets inside a character are
+/// moved back to its start.
+pub(crate) fn line_col(source: &str, pos: usize) -> (usize, usize) {
+    let mut pos = pos.min(source.len());
+    while !source.is_char_boundary(pos) {
+        pos -= 1;
+    }
+    let before = &source[..pos];
+    let line = before.matches('\n').count() + 1;
+    let start = before.rfind('\n').map_or(0, |i| i + 1);
+    (line, before[start..].chars().count() + 1)
+}
+
+impl From<Range<usize>> for Span {
+    fn from(range: Range<usize>) -> Self {
+        Span::new(range.start, range.end)
+    }
+}
+
+impl From<Span> for Range<usize> {
+    fn from(span: Span) -> Self {
+        span.range()
+    }
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+/// A value along with the span of input that it was parsed from.
+///
+/// See [`Parser::spanned`](crate::Parser::spanned).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Spanned<T, S = Span> {
+    pub(crate) value: T,
+    pub(crate) span: S,
+}
+
+impl<T, S> Spanned<T, S> {
+    /// Wrap a value with the span it came from.
+    pub fn new(value: T, span: S) -> Self {
+        Spanned { value, span }
+    }
+
+    /// The wrapped value.
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// The span of input that the value was parsed from.
+    pub fn span(&self) -> S
+    where
+        S: Clone,
+    {
+        self.span.clone()
+    }
+
+    /// Unwrap the value, discarding its span.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Map the wrapped value, keeping its span.
+    pub fn map<U, F>(self, f: F) -> Spanned<U, S>
+    where
+        F: FnOnce(T) -> U,
+    {
+        Spanned::new(f(self.value), self.span)
+    }
+}
@@ -1,8 +1,26 @@
 //! Utility module defining methods for parsing plain text.
+//!
+//! The parsers in this module work with string slices. [`generic`] has versions of them that
+//! work with any [`Stream`] of `char`s, such as a [`Reader`](crate::reader::Reader). The numeric
+//! parsers, [`number`] and [`float`], only work with string slices.
 
-use std::{marker::PhantomData, str::FromStr};
+use std::{marker::PhantomData, str::FromStr, sync::Arc};
 
-use crate::{combinator::Repeated, Input, Parser};
+use crate::{combinator::Repeated, Error, Expected, Input, Parser, Source, Stream};
+
+/// Match the given token at the given position, returning the position after it.
+fn match_token<S>(source: &S, mut pos: usize, token: &str) -> Option<usize>
+where
+    S: Stream<Item = char>,
+{
+    for expected in token.chars() {
+        match source.next(pos) {
+            Some((c, next)) if c == expected => pos = next,
+            _ => return None,
+        }
+    }
+    Some(pos)
+}
 
 /// See [`just`].
 #[derive(Clone, Copy)]
@@ -10,13 +28,17 @@ struct Just {
     token: &'static str,
 }
 
-impl<'a> Parser<'a, &'a str, ()> for Just {
-    fn parse(&self, input: &mut Input<'a, &'a str>) -> Result<(), ()> {
-        if input.source[input.pos..input.pos + self.token.len()] == *self.token {
-            input.pos += self.token.len();
-            Ok(())
-        } else {
-            Err(())
+impl<'a, S> Parser<'a, S, ()> for Just
+where
+    S: Stream<Item = char>,
+{
+    fn parse(&self, input: &mut Input<'a, S>) -> Result<(), Error> {
+        match match_token(&input.source, input.pos, self.token) {
+            Some(end) => {
+                input.pos = end;
+                Ok(())
+            }
+            None => Err(input.error([Expected::Just(self.token)])),
         }
     }
 }
@@ -29,11 +51,15 @@ impl<'a> Parser<'a, &'a str, ()> for Just {
 /// # Example
 ///
 /// ```
-/// let parser = just("hello").then(repeated(just("world")));
-/// let input = "helloworldworld";
-/// parser.parse(input.into());
+/// use aocparse::{text::just, Parser};
+///
+/// let parser = just("hello").then(just("world").repeated());
+/// assert!(parser.parse_str("helloworldworld").is_ok());
 /// ```
-pub fn just<'a>(token: &'static str) -> impl Parser<'a, &'a str, ()> {
+pub fn just<'a, S>(token: &'static str) -> impl Parser<'a, S, ()>
+where
+    S: Stream<Item = char>,
+{
     Just { token }
 }
 
@@ -42,83 +68,365 @@ pub struct OneOf {
     tokens: &'static [&'static str],
 }
 
-impl<'a> Parser<'a, &'a str, &'a str> for OneOf {
-    fn parse(&self, input: &mut Input<'a, &'a str>) -> Result<&'a str, ()> {
+impl<'a, S> Parser<'a, S, &'a str> for OneOf
+where
+    S: Stream<Item = char>,
+{
+    fn parse(&self, input: &mut Input<'a, S>) -> Result<&'a str, Error> {
         for token in self.tokens {
-            if input.source[input.pos..input.pos + token.len()] == **token {
-                input.pos += token.len();
+            if let Some(end) = match_token(&input.source, input.pos, token) {
+                input.pos = end;
                 return Ok(token);
             }
         }
-        Err(())
+        Err(input.error(self.tokens.iter().map(|token| Expected::Just(token))))
     }
 }
 
 /// Returns a parser that matches any of the given tokens.
-pub fn one_of<'a>(tokens: &'static [&'static str]) -> impl Parser<'a, &'a str, &'a str> {
+pub fn one_of<'a, S>(tokens: &'static [&'static str]) -> impl Parser<'a, S, &'a str>
+where
+    S: Stream<Item = char>,
+{
     OneOf { tokens }
 }
 
+/// Get the character at the current position of the input, along with the position after it.
+fn peek_char<S>(input: &Input<'_, S>) -> Option<(char, usize)>
+where
+    S: Stream<Item = char>,
+{
+    input.source.next(input.pos)
+}
+
+/// Consume characters while they satisfy the given predicate, returning the consumed slice.
+fn consume_while<S>(input: &mut Input<'_, S>, f: impl Fn(char) -> bool) -> S::Slice
+where
+    S: Stream<Item = char>,
+{
+    let start = input.pos;
+    while let Some((_, next)) = peek_char(input).filter(|(c, _)| f(*c)) {
+        input.pos = next;
+    }
+    input.source.slice(start, input.pos)
+}
+
 /// See [`ascii`].
 #[derive(Clone, Copy)]
 pub struct Ascii;
 
-impl<'a> Parser<'a, &'a str, &'a str> for Ascii {
-    fn parse(&self, input: &mut Input<'a, &'a str>) -> Result<&'a str, ()> {
-        let mut pos = input.pos;
-        while pos < input.source.len() {
-            if input.source[pos..pos + 1]
-                .chars()
-                .next()
-                .unwrap()
-                .is_ascii()
-            {
-                pos += 1;
-            } else {
-                break;
-            }
-        }
-        if pos == input.pos {
-            Err(())
+impl<'a, S> Parser<'a, S, S::Slice> for Ascii
+where
+    S: Stream<Item = char>,
+{
+    fn parse(&self, input: &mut Input<'a, S>) -> Result<S::Slice, Error> {
+        let start = input.pos;
+        let ascii = consume_while(input, |c| c.is_ascii());
+        if input.pos == start {
+            Err(input.error([Expected::Description("an ASCII character")]))
         } else {
-            input.pos = pos;
-            Ok(&input.source[input.pos..pos])
+            Ok(ascii)
         }
     }
 }
 
 /// A parser that matches one or more ASCII characters.
 pub fn ascii<'a>() -> impl Parser<'a, &'a str, &'a str> {
-    Ascii
+    generic::ascii()
+}
+
+/// A parser that matches a single character satisfying a predicate.
+struct CharClass<F> {
+    f: Arc<F>,
+    expected: Expected,
+}
+
+impl<F> Clone for CharClass<F> {
+    fn clone(&self) -> Self {
+        CharClass {
+            f: Arc::clone(&self.f),
+            expected: self.expected.clone(),
+        }
+    }
+}
+
+impl<'a, S, F> Parser<'a, S, S::Slice> for CharClass<F>
+where
+    S: Stream<Item = char>,
+    F: Fn(char) -> bool,
+{
+    fn parse(&self, input: &mut Input<'a, S>) -> Result<S::Slice, Error> {
+        match peek_char(input).filter(|(c, _)| (self.f)(*c)) {
+            Some((_, next)) => {
+                let start = input.pos;
+                input.pos = next;
+                Ok(input.source.slice(start, next))
+            }
+            None => Err(input.error([self.expected.clone()])),
+        }
+    }
+}
+
+/// A parser that matches any single character.
+pub fn any<'a>() -> impl Parser<'a, &'a str, &'a str> {
+    generic::any()
+}
+
+/// A parser that matches the given character.
+pub fn char<'a>(c: char) -> impl Parser<'a, &'a str, &'a str> {
+    generic::char(c)
+}
+
+/// A parser that matches a single character satisfying the given predicate.
+///
+/// # Example
+/// ```
+/// use aocparse::{text::filter, Parser};
+///
+/// let vowel = filter(|c| "aeiou".contains(c));
+/// assert_eq!(vowel.parse_str("apple"), Ok("a"));
+/// assert!(vowel.parse_str("pear").is_err());
+/// ```
+pub fn filter<'a, F>(f: F) -> impl Parser<'a, &'a str, &'a str>
+where
+    F: Fn(char) -> bool,
+{
+    generic::filter(f)
+}
+
+/// A parser that matches a single digit in the given radix.
+///
+/// # Panics
+/// Panics if the radix is not between 2 and 36.
+pub fn digit<'a>(radix: u32) -> impl Parser<'a, &'a str, &'a str> {
+    generic::digit(radix)
+}
+
+/// A parser that matches a single alphabetic character.
+pub fn alpha<'a>() -> impl Parser<'a, &'a str, &'a str> {
+    generic::alpha()
+}
+
+/// A parser that matches a single alphanumeric character.
+pub fn alphanumeric<'a>() -> impl Parser<'a, &'a str, &'a str> {
+    generic::alphanumeric()
+}
+
+/// A parser that matches a single character that is not one of the given characters.
+pub fn none_of<'a>(chars: &'static str) -> impl Parser<'a, &'a str, &'a str> {
+    generic::none_of(chars)
+}
+
+/// See [`ident`].
+#[derive(Clone, Copy)]
+struct Ident;
+
+impl<'a, S> Parser<'a, S, S::Slice> for Ident
+where
+    S: Stream<Item = char>,
+{
+    fn parse(&self, input: &mut Input<'a, S>) -> Result<S::Slice, Error> {
+        match peek_char(input) {
+            Some((c, next)) if c.is_alphabetic() || c == '_' => {
+                let start = input.pos;
+                input.pos = next;
+                consume_while(input, |c| c.is_alphanumeric() || c == '_');
+                Ok(input.source.slice(start, input.pos))
+            }
+            _ => Err(input.error([Expected::Description("an identifier")])),
+        }
+    }
+}
+
+/// A parser that matches an identifier.
+///
+/// Identifiers start with an alphabetic character or an underscore, followed by any number of
+/// alphanumeric characters or underscores.
+pub fn ident<'a>() -> impl Parser<'a, &'a str, &'a str> {
+    generic::ident()
+}
+
+/// See [`take_while`].
+struct TakeWhile<F> {
+    f: Arc<F>,
+}
+
+impl<F> Clone for TakeWhile<F> {
+    fn clone(&self) -> Self {
+        TakeWhile {
+            f: Arc::clone(&self.f),
+        }
+    }
+}
+
+impl<'a, S, F> Parser<'a, S, S::Slice> for TakeWhile<F>
+where
+    S: Stream<Item = char>,
+    F: Fn(char) -> bool,
+{
+    fn parse(&self, input: &mut Input<'a, S>) -> Result<S::Slice, Error> {
+        Ok(consume_while(input, &*self.f))
+    }
+}
+
+/// A parser that matches zero or more characters satisfying the given predicate.
+///
+/// # Example
+/// ```
+/// use aocparse::{text::take_while, Parser};
+///
+/// let word = take_while(|c| c.is_alphabetic());
+/// assert_eq!(word.parse_prefix("héllo wörld"), Ok(("héllo", " wörld")));
+/// ```
+pub fn take_while<'a, F>(f: F) -> impl Parser<'a, &'a str, &'a str>
+where
+    F: Fn(char) -> bool,
+{
+    generic::take_while(f)
+}
+
+/// See [`take_until`].
+#[derive(Clone, Copy)]
+struct TakeUntil {
+    token: &'static str,
+}
+
+impl<'a, S> Parser<'a, S, S::Slice> for TakeUntil
+where
+    S: Stream<Item = char>,
+{
+    fn parse(&self, input: &mut Input<'a, S>) -> Result<S::Slice, Error> {
+        let start = input.pos;
+        let mut pos = start;
+        loop {
+            if match_token(&input.source, pos, self.token).is_some() {
+                input.pos = pos;
+                return Ok(input.source.slice(start, pos));
+            }
+            match input.source.next(pos) {
+                Some((_, next)) => pos = next,
+                None => return Err(Error::new(pos, [Expected::Just(self.token)], None)),
+            }
+        }
+    }
+}
+
+/// A parser that matches all characters up to, but not including, the given token.
+///
+/// This fails if the token does not occur in the remaining input.
+///
+/// # Example
+/// ```
+/// use aocparse::{text::take_until, Parser};
+///
+/// let name = take_until(":");
+/// assert_eq!(name.parse_prefix("Card 1: 41 48"), Ok(("Card 1", ": 41 48")));
+/// ```
+pub fn take_until<'a>(token: &'static str) -> impl Parser<'a, &'a str, &'a str> {
+    generic::take_until(token)
 }
 
 /// See [`number`].
 #[derive(Clone, Copy)]
 pub struct Number<T> {
-    pub radix: T,
-    pub __phantom: PhantomData<T>,
+    pub(crate) radix: u32,
+    pub(crate) allow_prefix: bool,
+    pub(crate) allow_separators: bool,
+    pub(crate) __phantom: PhantomData<fn() -> T>,
+}
+
+impl<T> Number<T> {
+    /// Allow the radix to be given by a `0x`, `0o` or `0b` prefix, overriding the default.
+    pub fn allow_prefix(self) -> Self {
+        Number {
+            allow_prefix: true,
+            ..self
+        }
+    }
+
+    /// Allow digits to be separated by underscores, such as in `1_000_000`.
+    pub fn allow_separators(self) -> Self {
+        Number {
+            allow_separators: true,
+            ..self
+        }
+    }
+
+    /// Parse an optional radix prefix at the given position, returning the position after it
+    /// and the radix to use.
+    fn prefix(&self, input: &Input<'_, &str>, pos: usize, radix: u32) -> (usize, u32) {
+        if !self.allow_prefix {
+            return (pos, radix);
+        }
+        let rest = input.source.get(pos..).unwrap_or_default();
+        let prefixed = match rest.get(..2) {
+            Some("0x" | "0X") => 16,
+            Some("0o" | "0O") => 8,
+            Some("0b" | "0B") => 2,
+            _ => return (pos, radix),
+        };
+        // only treat the prefix as such if it is followed by a digit
+        match rest[2..].chars().next() {
+            Some(c) if c.is_digit(prefixed) => (pos + 2, prefixed),
+            _ => (pos, radix),
+        }
+    }
+
+    /// Parse the digits of an integer starting at the given position, passing each digit to
+    /// `push`, and returning the position after the last digit.
+    ///
+    /// If `push` returns `None`, the integer has overflowed and an error is returned.
+    fn digits(
+        &self,
+        input: &Input<'_, &str>,
+        pos: usize,
+        radix: u32,
+        ty: &'static str,
+        mut push: impl FnMut(u32) -> Option<()>,
+    ) -> Result<usize, Error> {
+        let rest = input.source.get(pos..).unwrap_or_default();
+        let mut end = pos;
+        let mut chars = rest.char_indices().peekable();
+        while let Some((i, c)) = chars.next() {
+            if let Some(digit) = c.to_digit(radix) {
+                push(digit).ok_or_else(|| {
+                    Error::custom(
+                        input.pos,
+                        format!("number does not fit in {}", ty),
+                        input.source.found(input.pos),
+                    )
+                })?;
+                end = pos + i + 1;
+            } else if c == '_' && self.allow_separators && end > pos {
+                // separators must be followed by another digit
+                match chars.peek() {
+                    Some((_, next)) if next.is_digit(radix) => continue,
+                    _ => break,
+                }
+            } else {
+                break;
+            }
+        }
+        if end == pos {
+            let found = input.source.found(pos);
+            Err(Error::new(pos, [Expected::Description("a digit")], found))
+        } else {
+            Ok(end)
+        }
+    }
 }
 
 macro_rules! unsigned_number_impl {
     ($t:ty) => {
         impl<'a> Parser<'a, &'a str, $t> for Number<$t> {
-            fn parse(&self, input: &mut Input<'a, &'a str>) -> Result<$t, ()> {
-                let mut pos = input.pos;
-                let mut value = 0;
-                while pos < input.source.len() {
-                    let digit = input.source[pos..pos + 1].parse::<$t>().map_err(|_| ())?;
-                    if digit >= self.radix {
-                        break;
-                    }
-                    value = value * self.radix + digit;
-                    pos += 1;
-                }
-                if pos == input.pos {
-                    Err(())
-                } else {
-                    input.pos = pos;
-                    Ok(value)
-                }
+            fn parse(&self, input: &mut Input<'a, &'a str>) -> Result<$t, Error> {
+                let (pos, radix) = self.prefix(input, input.pos, self.radix);
+                let mut value: $t = 0;
+                input.pos = self.digits(input, pos, radix, stringify!($t), |digit| {
+                    value = value.checked_mul(radix as $t)?.checked_add(digit as $t)?;
+                    Some(())
+                })?;
+                Ok(value)
             }
         }
     };
@@ -134,28 +442,25 @@ unsigned_number_impl!(usize);
 macro_rules! signed_number_impl {
     ($t:ty) => {
         impl<'a> Parser<'a, &'a str, $t> for Number<$t> {
-            fn parse(&self, input: &mut Input<'a, &'a str>) -> Result<$t, ()> {
-                let mut pos = input.pos;
-                let mut value = 0;
-                let mut sign = 1;
-                if input.source[pos..pos + 1] == *"-" {
-                    sign = -1;
-                    pos += 1;
-                }
-                while pos < input.source.len() {
-                    let digit = input.source[pos..pos + 1].parse::<$t>().map_err(|_| ())?;
-                    if digit >= self.radix {
-                        break;
-                    }
-                    value = value * self.radix + digit;
-                    pos += 1;
-                }
-                if pos == input.pos {
-                    Err(())
-                } else {
-                    input.pos = pos;
-                    Ok(value * sign)
-                }
+            fn parse(&self, input: &mut Input<'a, &'a str>) -> Result<$t, Error> {
+                let (pos, negative) = match input.source.get(input.pos..input.pos + 1) {
+                    Some("-") => (input.pos + 1, true),
+                    Some("+") => (input.pos + 1, false),
+                    _ => (input.pos, false),
+                };
+                let (pos, radix) = self.prefix(input, pos, self.radix);
+                // accumulate negative numbers below zero, so that the minimum value fits
+                let mut value: $t = 0;
+                input.pos = self.digits(input, pos, radix, stringify!($t), |digit| {
+                    let scaled = value.checked_mul(radix as $t)?;
+                    value = if negative {
+                        scaled.checked_sub(digit as $t)?
+                    } else {
+                        scaled.checked_add(digit as $t)?
+                    };
+                    Some(())
+                })?;
+                Ok(value)
             }
         }
     };
@@ -166,27 +471,298 @@ signed_number_impl!(i16);
 signed_number_impl!(i32);
 signed_number_impl!(i64);
 signed_number_impl!(i128);
+signed_number_impl!(isize);
 
-/// A parser that matches a number in the given radix.
+/// A parser that matches an integer in the given radix.
 ///
 /// This parser is generic, and is capable of parsing numbers into all signed and
-/// unsigned integer primitives.
+/// unsigned integer primitives. Radices from 2 to 36 are supported, using the letters `a` to `z`
+/// (in either case) for digits above 9. Signed integers may be preceded by `+` or `-`.
+///
+/// Integers that do not fit in the output type produce an error rather than overflowing. A
+/// radix prefix and digit separators can be enabled using [`Number::allow_prefix`] and
+/// [`Number::allow_separators`].
+///
+/// # Panics
+/// Panics if the radix is not between 2 and 36.
 ///
 /// # Example
 /// ```
-/// let integer = number::<u32>();
+/// use aocparse::{text::number, Parser};
+///
+/// let integer = number::<u32>(10);
+/// assert_eq!(integer.parse_str("1234"), Ok(1234));
+///
+/// let hex = number::<i64>(10).allow_prefix().allow_separators();
+/// assert_eq!(hex.parse_str("-0xff_ff"), Ok(-65535));
+/// assert!(number::<u8>(10).parse_str("256").is_err());
 /// ```
 pub fn number<T>(radix: T) -> Number<T>
 where
-    T: FromStr,
+    T: FromStr + TryInto<u32>,
 {
+    let radix = radix
+        .try_into()
+        .ok()
+        .filter(|radix| (2..=36).contains(radix))
+        .expect("radix must be between 2 and 36");
     Number {
         radix,
+        allow_prefix: false,
+        allow_separators: false,
+        __phantom: PhantomData,
+    }
+}
+
+/// See [`float`].
+#[derive(Clone, Copy)]
+pub struct Float<T> {
+    pub(crate) allow_separators: bool,
+    pub(crate) __phantom: PhantomData<fn() -> T>,
+}
+
+impl<T> Float<T> {
+    /// Allow digits to be separated by underscores, such as in `1_000.5`.
+    pub fn allow_separators(self) -> Self {
+        Float {
+            allow_separators: true,
+            ..self
+        }
+    }
+
+    /// Find the end of the digits starting at the given position.
+    fn digits(&self, source: &str, pos: usize) -> usize {
+        let mut end = pos;
+        let bytes = source.as_bytes();
+        while end < bytes.len() {
+            match bytes[end] {
+                b'0'..=b'9' => end += 1,
+                b'_' if self.allow_separators
+                    && end > pos
+                    && bytes.get(end + 1).is_some_and(u8::is_ascii_digit) =>
+                {
+                    end += 1
+                }
+                _ => break,
+            }
+        }
+        end
+    }
+
+    /// Find the end of a floating point number starting at the given position, if there is one.
+    fn scan(&self, source: &str, start: usize) -> Option<usize> {
+        let bytes = source.as_bytes();
+        let mut pos = start;
+        if matches!(bytes.get(pos), Some(b'+' | b'-')) {
+            pos += 1;
+        }
+        let integer = self.digits(source, pos);
+        let mut end = integer;
+        let mut has_digits = integer > pos;
+        if bytes.get(end) == Some(&b'.') {
+            let fraction = self.digits(source, end + 1);
+            if fraction > end + 1 || has_digits {
+                has_digits |= fraction > end + 1;
+                end = fraction;
+            }
+        }
+        if !has_digits {
+            return None;
+        }
+        if matches!(bytes.get(end), Some(b'e' | b'E')) {
+            let mut exponent = end + 1;
+            if matches!(bytes.get(exponent), Some(b'+' | b'-')) {
+                exponent += 1;
+            }
+            let digits = self.digits(source, exponent);
+            if digits > exponent {
+                end = digits;
+            }
+        }
+        Some(end)
+    }
+}
+
+macro_rules! float_impl {
+    ($t:ty) => {
+        impl<'a> Parser<'a, &'a str, $t> for Float<$t> {
+            fn parse(&self, input: &mut Input<'a, &'a str>) -> Result<$t, Error> {
+                let text = match self
+                    .scan(input.source, input.pos)
+                    .and_then(|end| input.source.get(input.pos..end))
+                {
+                    Some(text) => text,
+                    None => return Err(input.error([Expected::Description("a number")])),
+                };
+                let end = input.pos + text.len();
+                let value = if self.allow_separators {
+                    text.replace('_', "").parse::<$t>()
+                } else {
+                    text.parse::<$t>()
+                };
+                match value {
+                    Ok(value) => {
+                        input.pos = end;
+                        Ok(value)
+                    }
+                    Err(e) => Err(Error::custom(input.pos, e, input.source.found(input.pos))),
+                }
+            }
+        }
+    };
+}
+
+float_impl!(f32);
+float_impl!(f64);
+
+/// A parser that matches a decimal floating point number.
+///
+/// Numbers may have a leading `+` or `-`, a fractional part and an exponent, such as `-1.5e-3`.
+///
+/// # Example
+/// ```
+/// use aocparse::{text::float, Parser};
+///
+/// assert_eq!(float::<f64>().parse_str("-1.5e3"), Ok(-1500.0));
+/// assert_eq!(float::<f32>().parse_prefix("2.x"), Ok((2.0, "x")));
+/// ```
+pub fn float<T>() -> Float<T>
+where
+    T: FromStr,
+{
+    Float {
+        allow_separators: false,
         __phantom: PhantomData,
     }
 }
 
 /// A utility parser that consumes whitespace.
-pub fn whitespace<'a>() -> Repeated<impl Parser<'a, &'a str, ()>, ()> {
+pub fn whitespace<'a, S>() -> Repeated<impl Parser<'a, S, ()>, ()>
+where
+    S: Stream<Item = char>,
+{
     one_of(&["\t", "\r", "\n", " "]).ignored().repeated()
 }
+
+/// Versions of the parsers in this module that work with any [`Stream`] of `char`s.
+///
+/// These produce slices of the stream, such as the `String`s produced by a
+/// [`Reader`](crate::reader::Reader). [`just`], [`one_of`] and [`whitespace`] already work with
+/// any stream of `char`s, and are re-exported here for convenience.
+pub mod generic {
+    use super::*;
+
+    pub use super::{just, one_of, whitespace};
+
+    /// See [`ascii`](super::ascii).
+    pub fn ascii<'a, S>() -> impl Parser<'a, S, S::Slice>
+    where
+        S: Stream<Item = char>,
+    {
+        Ascii
+    }
+
+    /// See [`any`](super::any).
+    pub fn any<'a, S>() -> impl Parser<'a, S, S::Slice>
+    where
+        S: Stream<Item = char>,
+    {
+        CharClass {
+            f: Arc::new(|_| true),
+            expected: Expected::Description("any character"),
+        }
+    }
+
+    /// See [`char`](super::char).
+    pub fn char<'a, S>(c: char) -> impl Parser<'a, S, S::Slice>
+    where
+        S: Stream<Item = char>,
+    {
+        CharClass {
+            f: Arc::new(move |other| other == c),
+            expected: Expected::Char(c),
+        }
+    }
+
+    /// See [`filter`](super::filter).
+    pub fn filter<'a, S, F>(f: F) -> impl Parser<'a, S, S::Slice>
+    where
+        S: Stream<Item = char>,
+        F: Fn(char) -> bool,
+    {
+        CharClass {
+            f: Arc::new(f),
+            expected: Expected::Description("a matching character"),
+        }
+    }
+
+    /// See [`digit`](super::digit).
+    pub fn digit<'a, S>(radix: u32) -> impl Parser<'a, S, S::Slice>
+    where
+        S: Stream<Item = char>,
+    {
+        assert!((2..=36).contains(&radix), "radix must be between 2 and 36");
+        CharClass {
+            f: Arc::new(move |c: char| c.is_digit(radix)),
+            expected: Expected::Description("a digit"),
+        }
+    }
+
+    /// See [`alpha`](super::alpha).
+    pub fn alpha<'a, S>() -> impl Parser<'a, S, S::Slice>
+    where
+        S: Stream<Item = char>,
+    {
+        CharClass {
+            f: Arc::new(char::is_alphabetic),
+            expected: Expected::Description("an alphabetic character"),
+        }
+    }
+
+    /// See [`alphanumeric`](super::alphanumeric).
+    pub fn alphanumeric<'a, S>() -> impl Parser<'a, S, S::Slice>
+    where
+        S: Stream<Item = char>,
+    {
+        CharClass {
+            f: Arc::new(char::is_alphanumeric),
+            expected: Expected::Description("an alphanumeric character"),
+        }
+    }
+
+    /// See [`none_of`](super::none_of).
+    pub fn none_of<'a, S>(chars: &'static str) -> impl Parser<'a, S, S::Slice>
+    where
+        S: Stream<Item = char>,
+    {
+        CharClass {
+            f: Arc::new(move |c| !chars.contains(c)),
+            expected: Expected::NoneOf(chars),
+        }
+    }
+
+    /// See [`ident`](super::ident).
+    pub fn ident<'a, S>() -> impl Parser<'a, S, S::Slice>
+    where
+        S: Stream<Item = char>,
+    {
+        Ident
+    }
+
+    /// See [`take_while`](super::take_while).
+    pub fn take_while<'a, S, F>(f: F) -> impl Parser<'a, S, S::Slice>
+    where
+        S: Stream<Item = char>,
+        F: Fn(char) -> bool,
+    {
+        TakeWhile { f: Arc::new(f) }
+    }
+
+    /// See [`take_until`](super::take_until).
+    pub fn take_until<'a, S>(token: &'static str) -> impl Parser<'a, S, S::Slice>
+    where
+        S: Stream<Item = char>,
+    {
+        TakeUntil { token }
+    }
+}
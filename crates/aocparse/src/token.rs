@@ -0,0 +1,195 @@
+//! Utility module defining methods for parsing slices of tokens, such as those produced by a
+//! [`Lexer`](crate::lexer::Lexer).
+//!
+//! Positions in an `Input<&[T]>`, and in the errors produced while parsing one, are token
+//! indices. The spans returned by the lexer can be used to map them back to the source text.
+
+use std::{fmt::Debug, sync::Arc};
+
+use crate::{span::Span, Error, Expected, Input, Parser, Source, Stream};
+
+/// A token that can be parsed from a slice.
+///
+/// Tokens are described in errors using their [`Debug`] representation. The trait has no
+/// required methods, so it can be implemented with an empty `impl` block.
+pub trait Token: Clone + Debug {
+    /// Describe the token for use in error messages.
+    fn describe(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+impl<T: Token> Source for &[T] {
+    fn found(&self, pos: usize) -> Option<String> {
+        self.get(pos).map(Token::describe)
+    }
+
+    fn rest(&self, pos: usize) -> Option<String> {
+        let rest = self.get(pos..).filter(|rest| !rest.is_empty())?;
+        let rest: Vec<_> = rest.iter().map(Token::describe).collect();
+        Some(rest.join(" "))
+    }
+}
+
+impl<'a, T: Token> Stream for &'a [T] {
+    type Item = T;
+    type Slice = &'a [T];
+    type Span = Span;
+
+    fn next(&self, pos: usize) -> Option<(T, usize)> {
+        self.get(pos).map(|token| (token.clone(), pos + 1))
+    }
+
+    fn slice(&self, start: usize, end: usize) -> &'a [T] {
+        self.get(start..end).unwrap_or_default()
+    }
+
+    fn span(&self, start: usize, end: usize) -> Span {
+        Span::new(start, end)
+    }
+}
+
+/// See [`just`].
+#[derive(Clone)]
+struct Just<T> {
+    token: T,
+}
+
+impl<'a, T> Parser<'a, &'a [T], ()> for Just<T>
+where
+    T: Token + PartialEq,
+{
+    fn parse(&self, input: &mut Input<'a, &'a [T]>) -> Result<(), Error> {
+        if input.source.get(input.pos) == Some(&self.token) {
+            input.pos += 1;
+            Ok(())
+        } else {
+            Err(input.error([Expected::Token(self.token.describe())]))
+        }
+    }
+}
+
+/// A parser that matches a single instance of the given token.
+///
+/// # Example
+/// ```
+/// use aocparse::{token::{just, Token}, Input, Parser};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// enum Tok {
+///     Plus,
+///     Minus,
+/// }
+///
+/// impl Token for Tok {}
+///
+/// let tokens = [Tok::Plus, Tok::Plus, Tok::Minus];
+/// let plus = just(Tok::Plus).repeated();
+/// assert_eq!(plus.parse(&mut Input::from(&tokens[..])), Ok(vec![(), ()]));
+/// ```
+pub fn just<'a, T>(token: T) -> impl Parser<'a, &'a [T], ()>
+where
+    T: Token + PartialEq + 'a,
+{
+    Just { token }
+}
+
+/// See [`any`].
+#[derive(Clone, Copy)]
+struct Any;
+
+impl<'a, T: Token> Parser<'a, &'a [T], T> for Any {
+    fn parse(&self, input: &mut Input<'a, &'a [T]>) -> Result<T, Error> {
+        match input.source.get(input.pos) {
+            Some(token) => {
+                input.pos += 1;
+                Ok(token.clone())
+            }
+            None => Err(input.error([Expected::Description("any token")])),
+        }
+    }
+}
+
+/// A parser that matches any single token.
+pub fn any<'a, T: Token + 'a>() -> impl Parser<'a, &'a [T], T> {
+    Any
+}
+
+/// See [`select`].
+pub struct Select<F> {
+    pub(crate) f: Arc<F>,
+}
+
+impl<F> Clone for Select<F> {
+    fn clone(&self) -> Self {
+        Select {
+            f: Arc::clone(&self.f),
+        }
+    }
+}
+
+impl<'a, T, O, F> Parser<'a, &'a [T], O> for Select<F>
+where
+    T: Token,
+    F: Fn(T) -> Option<O>,
+{
+    fn parse(&self, input: &mut Input<'a, &'a [T]>) -> Result<O, Error> {
+        match input.source.get(input.pos).cloned().and_then(&*self.f) {
+            Some(output) => {
+                input.pos += 1;
+                Ok(output)
+            }
+            None => Err(input.error([Expected::Description("a matching token")])),
+        }
+    }
+}
+
+/// A parser that matches a single token for which the given function returns `Some`,
+/// producing the returned value.
+///
+/// This is usually used through the [`select!`](crate::select) macro. Use
+/// [`Parser::labelled`] to give the selected tokens a name in error messages.
+pub fn select<T, O, F>(f: F) -> Select<F>
+where
+    F: Fn(T) -> Option<O>,
+{
+    Select { f: Arc::new(f) }
+}
+
+/// Create a parser that matches a single token against the given patterns, producing the output
+/// of the first arm that matches.
+///
+/// The arms are written like those of a `match` expression, and may have guards. Bindings in the
+/// patterns extract payloads from the token.
+///
+/// # Example
+/// ```
+/// use aocparse::{select, token::Token, Input, Parser};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// enum Tok {
+///     Num(i64),
+///     Ident(String),
+///     Comma,
+/// }
+///
+/// impl Token for Tok {}
+///
+/// let value = select! {
+///     Tok::Num(n) => n,
+///     Tok::Ident(name) if name == "ten" => 10,
+/// };
+/// let values = value.separated_by(select! { Tok::Comma => () });
+///
+/// let tokens = [Tok::Num(1), Tok::Comma, Tok::Ident("ten".into())];
+/// assert_eq!(values.parse(&mut Input::from(&tokens[..])), Ok(vec![1, 10]));
+/// ```
+#[macro_export]
+macro_rules! select {
+    ($($pattern:pat $(if $guard:expr)? => $output:expr),+ $(,)?) => {
+        $crate::token::select(move |token| match token {
+            $($pattern $(if $guard)? => ::std::option::Option::Some($output),)+
+            _ => ::std::option::Option::None,
+        })
+    };
+}
@@ -0,0 +1,35 @@
+use aocparse::{
+    text::{just, number},
+    Expected, Parser,
+};
+
+#[test]
+fn or_backtracks_after_partial_match() {
+    let parser = just("a").then(just("b")).ignored().or(just("ac"));
+    assert_eq!(parser.parse_str("ac"), Ok(()));
+}
+
+#[test]
+fn cut_prevents_backtracking() {
+    let game = just("Game ").then(number::<u32>(10).cut()).ignored();
+    let parser = game.or(just("Game over"));
+    let error = parser.parse_str("Game over").unwrap_err();
+
+    assert!(error.is_committed());
+    assert_eq!(error.pos(), 5);
+    assert_eq!(
+        error.expected().collect::<Vec<_>>(),
+        [&Expected::Description("a digit")]
+    );
+}
+
+#[test]
+fn optional_and_repeated_rewind_failed_attempts() {
+    let pair = just("a").then(just("b"));
+    let parser = pair
+        .clone()
+        .repeated()
+        .then(pair.optional())
+        .then(just("ac"));
+    assert!(parser.parse_str("ababac").is_ok());
+}
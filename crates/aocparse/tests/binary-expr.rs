@@ -1,30 +1,56 @@
 use aocparse::{
+    pratt::{pratt, Assoc},
+    recursive::recursive,
     text::{just, number},
     Parser,
 };
 
+#[derive(Debug, PartialEq)]
 enum Op {
     Integer(i32),
     Plus(Box<Op>, Box<Op>),
     Times(Box<Op>, Box<Op>),
 }
 
+impl Op {
+    fn eval(&self) -> i32 {
+        match self {
+            Op::Integer(n) => *n,
+            Op::Plus(a, b) => a.eval() + b.eval(),
+            Op::Times(a, b) => a.eval() * b.eval(),
+        }
+    }
+}
+
 #[test]
 fn parse_expr() {
     // create parser
-    let integer = number(10).map(Op::Integer);
-    let mul = integer
-        .then(just("*").ignored())
-        .then(integer)
-        .map(|((a, _), b)| Op::Times(Box::new(a), Box::new(b)));
-
-    let atom = integer.or(mul.clone());
+    let parser = recursive(|expr| {
+        let integer = number(10).map(Op::Integer);
+        let atom = integer.or(expr.delimited_by(just("("), just(")")));
 
-    let add = atom
-        .clone()
-        .then(just("+").ignored())
-        .then(atom.clone())
-        .map(|((a, _), b)| Op::Plus(Box::new(a), Box::new(b)));
+        pratt(atom)
+            .infix(just("+"), Assoc::Left, 1, |a, b| {
+                Op::Plus(Box::new(a), Box::new(b))
+            })
+            .infix(just("*"), Assoc::Left, 2, |a, b| {
+                Op::Times(Box::new(a), Box::new(b))
+            })
+    });
 
-    let parser = add.or(mul).or(atom);
+    assert_eq!(
+        parser.parse_str("1+2*3"),
+        Ok(Op::Plus(
+            Box::new(Op::Integer(1)),
+            Box::new(Op::Times(
+                Box::new(Op::Integer(2)),
+                Box::new(Op::Integer(3))
+            ))
+        ))
+    );
+    assert_eq!(parser.parse_str("(1+2)*3").map(|op| op.eval()), Ok(9));
+    assert_eq!(
+        parser.parse_str("2*(3+(4*5))+1").map(|op| op.eval()),
+        Ok(47)
+    );
 }
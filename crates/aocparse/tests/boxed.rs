@@ -0,0 +1,83 @@
+use aocparse::{
+    boxed::{BoxedParser, SyncBoxedParser},
+    text::{alpha, just, number, whitespace},
+    Parser,
+};
+
+/// A grammar for AoC 2022 day 5 crane instructions, stored as struct fields.
+struct Grammar<'a> {
+    number: BoxedParser<'a, &'a str, usize>,
+    step: BoxedParser<'a, &'a str, (usize, usize, usize)>,
+}
+
+impl<'a> Grammar<'a> {
+    fn new() -> Self {
+        let number = number::<usize>(10).boxed();
+        let field = |name: &'static str| {
+            just(name)
+                .then(whitespace())
+                .then(number.clone())
+                .map(|(_, n)| n)
+        };
+        let step = field("move")
+            .then(just(" "))
+            .then(field("from"))
+            .then(just(" "))
+            .then(field("to"))
+            .map(|((((count, _), from), _), to)| (count, from, to))
+            .boxed();
+        Grammar { number, step }
+    }
+}
+
+fn operator<'a>(symbols: &[&'static str]) -> BoxedParser<'a, &'a str, &'a str> {
+    let mut parser = alpha().boxed();
+    for &symbol in symbols {
+        parser = just(symbol).to(symbol).or(parser).boxed();
+    }
+    parser
+}
+
+#[test]
+fn boxed_parsers_in_struct_fields() {
+    let grammar = Grammar::new();
+    assert_eq!(grammar.number.parse_str("42"), Ok(42));
+    assert_eq!(
+        grammar
+            .step
+            .clone()
+            .separated_by(just("\n"))
+            .parse_str_all("move 1 from 2 to 1\nmove 3 from 1 to 3"),
+        Ok(vec![(1, 2, 1), (3, 1, 3)])
+    );
+    assert!(grammar.step.parse_str("move x from 2 to 1").is_err());
+}
+
+#[test]
+fn boxed_parsers_built_in_loops() {
+    let op = operator(&["+", "-", "*"]);
+    assert_eq!(
+        op.clone().repeated().parse_str_all("+x*-"),
+        Ok(vec!["+", "x", "*", "-"])
+    );
+    assert!(op.parse_str_all("/").is_err());
+    assert_eq!(op.boxed().parse_str("*"), Ok("*"));
+}
+
+#[test]
+fn sync_boxed_parsers_across_threads() {
+    let pair: SyncBoxedParser<&str, (u32, u32)> = number::<u32>(10)
+        .then(just(","))
+        .then(number::<u32>(10))
+        .map(|((a, _), b)| (a, b))
+        .boxed_sync();
+    let pairs = pair.clone().boxed_sync();
+    assert_eq!(
+        pairs.parse_parallel("1,2\n3,4\n", "\n"),
+        Ok(vec![(1, 2), (3, 4)])
+    );
+    std::thread::scope(|scope| {
+        let handle = scope.spawn(|| pair.parse_str("5,6"));
+        assert_eq!(handle.join().unwrap(), Ok((5, 6)));
+    });
+}
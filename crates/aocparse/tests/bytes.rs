@@ -0,0 +1,173 @@
+use aocparse::{
+    bits::{bit, bits, Bits},
+    bytes::{be, byte, bytes, le, take},
+    Error, Expected, Input, Parser,
+};
+
+#[test]
+fn byte_primitives() {
+    let header = bytes(b"P6\n").then(take(3)).then(byte(b'\n'));
+    assert_eq!(
+        header.parse_bytes_all(b"P6\n3 2\n"),
+        Ok((((), &b"3 2"[..]), b'\n'))
+    );
+
+    let error = header.parse_bytes(b"P6\n3").unwrap_err();
+    assert_eq!(error.pos(), 4);
+    assert_eq!(error.found(), None);
+
+    let error = byte(b'a').parse_bytes_all(b"b\x00").unwrap_err();
+    assert_eq!(
+        error.expected().collect::<Vec<_>>(),
+        [&Expected::Byte(b'a')]
+    );
+    assert_eq!(
+        error.to_string(),
+        "at offset 0: expected `byte(0x61)`, found \"b\""
+    );
+
+    let error = byte(b'b').parse_bytes_all(b"b\x00").unwrap_err();
+    assert_eq!(error.found(), Some("\\x00"));
+}
+
+#[test]
+fn endian_integers() {
+    let data = [0x01, 0x02, 0x03, 0x04, 0xff, 0xfe];
+    let parser = be::<u32>().then(le::<i16>());
+    assert_eq!(parser.parse_bytes_all(&data), Ok((0x0102_0304, -257)));
+    assert_eq!(le::<u32>().parse_bytes(&data), Ok(0x0403_0201));
+    assert_eq!(
+        be::<u8>()
+            .repeated()
+            .parse_bytes_all(&data)
+            .map(|v| v.len()),
+        Ok(6)
+    );
+    assert!(le::<u128>().parse_bytes(&data).is_err());
+}
+
+#[test]
+fn bit_positions() {
+    let mut input = Input::from(Bits::new(&[0b1100_0000]));
+    assert_eq!(bits::<u8>(2).parse(&mut input), Ok(3));
+    assert_eq!(input.pos(), 2);
+
+    let error = bits::<u16>(7).parse(&mut input).unwrap_err();
+    assert_eq!(error.pos(), 8);
+    assert_eq!(input.pos(), 2);
+    assert_eq!(
+        bits::<u16>(6).then(bit().not()).parse(&mut input),
+        Ok((0, ()))
+    );
+}
+
+#[derive(Debug, PartialEq)]
+enum Packet {
+    Literal {
+        version: u8,
+        value: u64,
+    },
+    Operator {
+        version: u8,
+        type_id: u8,
+        packets: Vec<Packet>,
+    },
+}
+
+impl Packet {
+    fn version_sum(&self) -> u32 {
+        match self {
+            Packet::Literal { version, .. } => *version as u32,
+            Packet::Operator {
+                version, packets, ..
+            } => *version as u32 + packets.iter().map(Packet::version_sum).sum::<u32>(),
+        }
+    }
+}
+
+/// A parser for the BITS packets from AoC 2021 day 16.
+#[derive(Clone, Copy)]
+struct PacketParser;
+
+impl<'a> Parser<'a, Bits<'a>, Packet> for PacketParser {
+    fn parse(&self, input: &mut Input<'a, Bits<'a>>) -> Result<Packet, Error> {
+        let (version, type_id) = bits::<u8>(3).then(bits::<u8>(3)).parse(input)?;
+        if type_id == 4 {
+            let group = bit().then(bits::<u64>(4));
+            let mut value = 0;
+            loop {
+                let (more, digit) = group.parse(input)?;
+                value = value << 4 | digit;
+                if !more {
+                    return Ok(Packet::Literal { version, value });
+                }
+            }
+        }
+        let packets = if bit().parse(input)? {
+            let count = bits::<usize>(11).parse(input)?;
+            self.repeated().exactly(count).parse(input)?
+        } else {
+            let end = bits::<usize>(15).parse(input)? + input.pos();
+            let mut packets = Vec::new();
+            while input.pos() < end {
+                packets.push(self.parse(input)?);
+            }
+            packets
+        };
+        Ok(Packet::Operator {
+            version,
+            type_id,
+            packets,
+        })
+    }
+}
+
+fn decode_hex(hex: &str) -> Vec<u8> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+fn parse_packet(hex: &str) -> Packet {
+    let bytes = decode_hex(hex);
+    PacketParser.parse(&mut Bits::new(&bytes).into()).unwrap()
+}
+
+#[test]
+fn bits_packets() {
+    assert_eq!(
+        parse_packet("D2FE28"),
+        Packet::Literal {
+            version: 6,
+            value: 2021
+        }
+    );
+    assert_eq!(
+        parse_packet("38006F45291200"),
+        Packet::Operator {
+            version: 1,
+            type_id: 6,
+            packets: vec![
+                Packet::Literal {
+                    version: 6,
+                    value: 10
+                },
+                Packet::Literal {
+                    version: 2,
+                    value: 20
+                },
+            ],
+        }
+    );
+    assert_eq!(parse_packet("8A004A801A8002F478").version_sum(), 16);
+    assert_eq!(parse_packet("620080001611562C8802118E34").version_sum(), 12);
+    assert_eq!(
+        parse_packet("C0015000016115A2E0802F182340").version_sum(),
+        23
+    );
+    assert_eq!(
+        parse_packet("A0016C880162017C3686B18A3D4780").version_sum(),
+        31
+    );
+}
@@ -0,0 +1,106 @@
+use std::collections::{HashMap, HashSet};
+
+use aocparse::{
+    select,
+    text::{alpha, filter, just, number, take_while},
+    token::Token,
+    Input, Parser,
+};
+
+/// A set of characters, which deliberately cannot be cloned.
+struct Chars(&'static str);
+
+impl Chars {
+    fn contains(&self, c: char) -> bool {
+        self.0.contains(c)
+    }
+}
+
+#[test]
+fn closures_may_capture_owned_data() {
+    let digits = HashMap::from([("one", 1), ("two", 2), ("three", 3)]);
+    let word = alpha().repeated().at_least(1).to_slice();
+    let digit = word.try_map(move |name: &str| digits.get(name).copied().ok_or("not a digit"));
+
+    let banned = HashSet::from([2]);
+    let allowed = digit.clone().filter(move |n| !banned.contains(n));
+
+    let prefix = String::from("digits: ");
+    let labelled = allowed
+        .separated_by(just(","))
+        .map(move |digits| format!("{prefix}{digits:?}"));
+    assert_eq!(
+        labelled.parse_str("one,three"),
+        Ok("digits: [1, 3]".to_string())
+    );
+    assert!(labelled
+        .parse_str("one,two")
+        .is_ok_and(|s| s == "digits: [1]"));
+    assert_eq!(
+        digit.parse_str("four").unwrap_err().message(),
+        Some("not a digit")
+    );
+}
+
+#[test]
+fn folds_and_validations_may_capture_owned_data() {
+    let weights = vec![1, 10, 100];
+    let weighted = number::<u32>(10)
+        .separated_by(just(" "))
+        .map(move |ns| ns.iter().zip(&weights).map(|(n, w)| n * w).collect());
+    let sum = weighted.foldl(|a: u32, b| a + b);
+    assert_eq!(sum.parse_str("1 2 3"), Ok(321));
+
+    let limit = String::from("50");
+    let checked = number::<u32>(10).validate(move |n| {
+        if *n <= limit.parse().unwrap() {
+            Ok(())
+        } else {
+            Err(format!("{n} is above {limit}"))
+        }
+    });
+    let mut input = Input::from("42");
+    assert_eq!(checked.parse(&mut input), Ok(42));
+    assert_eq!(input.diagnostics().len(), 0);
+    let mut input = Input::from("95");
+    assert_eq!(checked.clone().parse(&mut input), Ok(95));
+    assert_eq!(input.diagnostics()[0].message(), Some("95 is above 50"));
+}
+
+#[test]
+fn shared_closures_work_across_threads() {
+    let scores = HashMap::from([('A', 1), ('B', 2), ('C', 3)]);
+    let score = alpha().map(move |c: &str| scores[&c.chars().next().unwrap()]);
+    let round = score.clone().then(just(" ")).then(score);
+    let round = round.map(|((a, _), b)| a * 10 + b);
+    assert_eq!(
+        round.parse_parallel("A B\nC A\nB C\n", "\n"),
+        Ok(vec![12, 31, 23])
+    );
+}
+
+#[test]
+fn character_and_token_predicates_need_not_be_clone() {
+    let vowels = Chars("aeiou");
+    let vowel = filter(move |c| vowels.contains(c));
+    let digits = Chars("0123456789");
+    let number = take_while(move |c| digits.contains(c));
+    let pair = vowel.clone().then(number).then(vowel);
+    assert_eq!(pair.parse_str("a12e"), Ok((("a", "12"), "e")));
+    assert!(pair.parse_str("a12x").is_err());
+
+    #[derive(Clone, Debug, PartialEq)]
+    enum Tok {
+        Letter(char),
+    }
+    impl Token for Tok {}
+
+    let letters = Chars("xyz");
+    let letter = select! { Tok::Letter(c) if letters.contains(c) => c };
+    let tokens = [Tok::Letter('x'), Tok::Letter('a')];
+    let parser = letter.clone().then(letter);
+    assert!(parser.parse(&mut Input::from(&tokens[..1])).is_err());
+    assert!(parser.parse(&mut Input::from(&tokens[..])).is_err());
+    let tokens = [Tok::Letter('x'), Tok::Letter('z')];
+    assert_eq!(parser.parse(&mut Input::from(&tokens[..])), Ok(('x', 'z')));
+}
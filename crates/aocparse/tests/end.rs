@@ -0,0 +1,39 @@
+use aocparse::{
+    primitive::end,
+    text::{just, number},
+    Expected, Input, Parser,
+};
+
+#[test]
+fn end_only_matches_at_end_of_input() {
+    assert_eq!(end().parse_str(""), Ok(()));
+
+    let error = end().parse_str("abc").unwrap_err();
+    assert_eq!(error.expected().collect::<Vec<_>>(), [&Expected::End]);
+    assert_eq!(error.found(), Some("a"));
+
+    let error = number::<u32>(10).parse_str_all("12abc").unwrap_err();
+    assert_eq!(error.found(), Some("abc"));
+}
+
+#[test]
+fn parse_str_all_reports_trailing_input() {
+    let parser = number::<u32>(10).separated_by(just(","));
+    assert_eq!(parser.parse_str_all("1,2,3"), Ok(vec![1, 2, 3]));
+
+    let error = parser.parse_str_all("1,2;3").unwrap_err();
+    assert_eq!(error.pos(), 3);
+    assert_eq!(error.found(), Some(";3"));
+    assert_eq!(
+        error.to_string(),
+        "at offset 3: expected `just(\",\")` or end of input, found \";3\""
+    );
+}
+
+#[test]
+fn parse_complete_rewinds_on_failure() {
+    let mut input = Input::from("12ab");
+    assert!(number::<u32>(10).parse_complete(&mut input).is_err());
+    assert_eq!(number::<u32>(10).parse_prefix("12ab"), Ok((12, "ab")));
+    assert_eq!(number::<u32>(10).parse_prefix("12"), Ok((12, "")));
+}
@@ -0,0 +1,118 @@
+use aocparse::{
+    text::{just, number, one_of},
+    Expected, Input, Parser,
+};
+
+#[test]
+fn records_position_expected_and_found() {
+    let parser = just("Game ").then(number::<u32>(10));
+    let error = parser.parse_str("Game x").unwrap_err();
+
+    assert_eq!(error.pos(), 5);
+    assert_eq!(
+        error.expected().collect::<Vec<_>>(),
+        [&Expected::Description("a digit")]
+    );
+    assert_eq!(error.found(), Some("x"));
+}
+
+#[test]
+fn or_merges_expected_sets() {
+    let parser = just("red").or(just("blue"));
+    let error = parser.parse_str("green").unwrap_err();
+
+    assert_eq!(error.pos(), 0);
+    assert_eq!(
+        error.expected().collect::<Vec<_>>(),
+        [&Expected::Just("blue"), &Expected::Just("red")]
+    );
+    assert_eq!(
+        error.to_string(),
+        "at offset 0: expected `just(\"blue\")` or `just(\"red\")`, found \"g\""
+    );
+}
+
+#[test]
+fn furthest_error_wins() {
+    let parser = number::<u32>(10)
+        .then(just(";"))
+        .ignored()
+        .or(just("x").ignored());
+    let error = parser.parse_str("12,").unwrap_err();
+
+    assert_eq!(error.pos(), 2);
+    assert_eq!(error.found(), Some(","));
+}
+
+#[test]
+fn reports_line_and_column() {
+    let source = "Game 1: 3 blue\r\nGame 2: 4 red\r\n";
+    let input = Input::from(source);
+    let error = just("Game").parse_str("").unwrap_err();
+    assert_eq!(error.report("").line_col(), (1, 1));
+
+    let error = just("Game 1: 3 blue\r\nGame 2: 4 ")
+        .then(just("blue"))
+        .parse_str(source)
+        .unwrap_err();
+    let report = input.report(&error);
+    assert_eq!(report.line_col(), (2, 11));
+    assert_eq!(
+        report.to_string(),
+        "error: expected `just(\"blue\")`, found \"r\"\n \
+         --> 2:11\n  \
+         |\n\
+         2 | Game 2: 4 red\n  \
+         |           ^\n"
+    );
+    assert_eq!(
+        report.colored(true).to_string().lines().last(),
+        Some("  \x1b[1;34m|\x1b[0m           \x1b[1;31m^\x1b[0m")
+    );
+}
+
+#[test]
+fn labels_replace_expectations_and_nest() {
+    let colour = one_of(&["red", "green", "blue"]).labelled("colour");
+    let draw = number::<u32>(10)
+        .then(just(" "))
+        .then(colour)
+        .labelled("draw");
+    let game = just("Game ")
+        .then(number::<u32>(10).labelled("game id"))
+        .then(just(": "))
+        .then(draw)
+        .labelled("game record");
+
+    let error = game.parse_str("Game 1: 3 pink").unwrap_err();
+    assert_eq!(error.pos(), 10);
+    assert_eq!(
+        error.expected().collect::<Vec<_>>(),
+        [&Expected::Label("colour")]
+    );
+    assert_eq!(error.context().collect::<Vec<_>>(), ["game record", "draw"]);
+    assert_eq!(
+        error.to_string(),
+        "at offset 10: in game record > in draw > expected colour, found \"p\""
+    );
+
+    let error = game.parse_str("Gme 1").unwrap_err();
+    assert_eq!(
+        error.expected().collect::<Vec<_>>(),
+        [&Expected::Label("game record")]
+    );
+    assert_eq!(error.context().count(), 0);
+}
+
+#[test]
+fn labels_keep_custom_messages() {
+    let small = number::<u32>(10)
+        .try_map(|n| if n < 5 { Ok(n) } else { Err("too big") })
+        .labelled("small");
+
+    let error = small.parse_str("9").unwrap_err();
+    assert_eq!(error.pos(), 0);
+    assert_eq!(error.message(), Some("too big"));
+    assert_eq!(error.context().collect::<Vec<_>>(), ["small"]);
+    assert_eq!(error.to_string(), "at offset 0: in small > too big");
+}
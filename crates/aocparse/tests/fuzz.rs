@@ -0,0 +1,145 @@
+//! Feeds random strings to every text primitive and combinator, checking that none of them
+//! panic, at the start of the input or after any number of characters.
+
+use aocparse::{
+    primitive::end,
+    text::{
+        alpha, alphanumeric, any, ascii, char, digit, filter, float, ident, just, none_of, number,
+        one_of, take_until, take_while, whitespace,
+    },
+    Parser,
+};
+
+/// Characters chosen to hit token prefixes, number syntax and multi-byte boundaries.
+const ALPHABET: &[char] = &[
+    'a', 'b', 'x', 'o', 'e', 'E', '0', '1', '7', '9', 'f', 'z', '_', '+', '-', '.', ',', ':', ' ',
+    '\n', '\r', '\t', 'é', 'ß', '→', '٣', '🦀',
+];
+
+/// A small xorshift generator, so that failures are reproducible.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn string(&mut self) -> String {
+        let len = self.below(12);
+        (0..len)
+            .map(|_| ALPHABET[self.below(ALPHABET.len())])
+            .collect()
+    }
+}
+
+/// Run the parser against the input with every entry point, starting after each character.
+fn exercise<'a, P, O>(parser: P, input: &'a str)
+where
+    P: Parser<'a, &'a str, O>,
+{
+    let _ = parser.parse_str(input);
+    let _ = parser.parse_prefix(input);
+    if let Err(error) = parser.parse_str_all(input) {
+        let _ = error.to_string();
+        let _ = error.report(input).colored(true).to_string();
+    }
+    for skip in 0..=input.chars().count() {
+        let offset = any().repeated().exactly(skip).then(parser.clone());
+        let _ = offset.parse_prefix(input);
+        let _ = offset.parse_str_all(input);
+    }
+}
+
+/// Run every integer parser against the input, in the given radix.
+fn exercise_numbers(input: &str, radix: u8) {
+    exercise(number::<u8>(radix), input);
+    exercise(number::<u16>(radix as u16), input);
+    exercise(number::<u32>(radix as u32).allow_prefix(), input);
+    exercise(number::<u64>(radix as u64).allow_separators(), input);
+    exercise(number::<u128>(radix as u128), input);
+    exercise(number::<usize>(radix as usize), input);
+    exercise(number::<i8>(radix as i8).allow_prefix(), input);
+    exercise(number::<i16>(radix as i16), input);
+    exercise(number::<i32>(radix as i32).allow_separators(), input);
+    exercise(
+        number::<i64>(radix as i64)
+            .allow_prefix()
+            .allow_separators(),
+        input,
+    );
+    exercise(number::<i128>(radix as i128), input);
+    exercise(number::<isize>(radix as isize), input);
+}
+
+#[test]
+fn text_primitives_never_panic() {
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+    for _ in 0..500 {
+        let input = rng.string();
+        let input = input.as_str();
+
+        exercise(just("0x"), input);
+        exercise(just("é"), input);
+        exercise(one_of(&["ab", "→", "🦀x"]), input);
+        exercise(ascii(), input);
+        exercise(any(), input);
+        exercise(char('ß'), input);
+        exercise(filter(|c| c.is_uppercase()), input);
+        exercise(digit(16), input);
+        exercise(alpha(), input);
+        exercise(alphanumeric(), input);
+        exercise(none_of(",:→"), input);
+        exercise(ident(), input);
+        exercise(take_while(|c| c != ' '), input);
+        exercise(take_until("→"), input);
+        exercise(whitespace(), input);
+        exercise(end(), input);
+        exercise(float::<f32>(), input);
+        exercise(float::<f64>().allow_separators(), input);
+        exercise_numbers(input, [2, 8, 10, 16, 36][rng.below(5)]);
+    }
+}
+
+#[test]
+fn combinators_never_panic() {
+    let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+    for _ in 0..300 {
+        let input = rng.string();
+        let input = input.as_str();
+        let item = number::<i32>(10).or(ident().map(|s| s.len() as i32));
+
+        exercise(item.clone().separated_by(just(",")).allow_trailing(), input);
+        exercise(
+            item.clone().repeated().at_least(1).foldl(|a, b| a + b),
+            input,
+        );
+        exercise(item.clone().then(just(":")).optional(), input);
+        exercise(item.clone().labelled("item").then(end()), input);
+        exercise(item.clone().rewind().and_is(any()), input);
+        exercise(item.clone().not().then(any()), input);
+        exercise(item.clone().memoized().repeated(), input);
+        exercise(
+            just("+")
+                .cut()
+                .then(item.clone())
+                .map(|(_, n)| n)
+                .or(item.clone()),
+            input,
+        );
+        exercise(item.clone().filter(|n| n % 2 == 0), input);
+        exercise(item.clone().try_map(u8::try_from), input);
+        exercise(
+            item.clone()
+                .validate(|n| if *n > 10 { Err("too large") } else { Ok(()) }),
+            input,
+        );
+        exercise(whitespace().then(item.clone()), input);
+    }
+}
@@ -0,0 +1,91 @@
+use std::io::Cursor;
+
+use aocparse::{
+    reader::Reader,
+    text::{generic, just, number, take_while},
+    Input, Parser,
+};
+
+#[test]
+fn records_with_separators() {
+    let elves = number::<u32>(10)
+        .separated_by(just("\n"))
+        .at_least(1)
+        .map(|calories| calories.into_iter().sum::<u32>());
+    let totals: Result<Vec<_>, _> = elves
+        .parse_iter("1000\n2000\n\n4000\n\n5000\n6000")
+        .separator(just("\n\n"))
+        .collect();
+    assert_eq!(totals, Ok(vec![3000, 4000, 11000]));
+
+    let numbers = number::<u32>(10).parse_iter("1,2,3").separator(just(","));
+    assert_eq!(numbers.map(Result::unwrap).collect::<Vec<_>>(), [1, 2, 3]);
+
+    assert_eq!(number::<u32>(10).parse_iter("").next(), None);
+}
+
+#[test]
+fn records_without_separators() {
+    let pairs: Vec<_> = take_while(|c: char| c.is_ascii_lowercase())
+        .then(number::<u8>(10))
+        .parse_iter("a1bc23d4")
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(pairs, [("a", 1), ("bc", 23), ("d", 4)]);
+}
+
+#[test]
+fn iteration_stops_at_the_first_error() {
+    let mut numbers = number::<u32>(10)
+        .parse_iter("1\n2\nx\n4\n")
+        .separator(just("\n"));
+    assert_eq!(numbers.next(), Some(Ok(1)));
+    assert_eq!(numbers.next(), Some(Ok(2)));
+    let error = numbers.next().unwrap().unwrap_err();
+    assert_eq!(error.pos(), 4);
+    assert_eq!(error.found(), Some("x"));
+    assert_eq!(numbers.next(), None);
+    assert_eq!(numbers.input().pos(), 4);
+
+    let mut numbers = number::<u32>(10).parse_iter("1 2").separator(just(","));
+    assert_eq!(numbers.next(), Some(Ok(1)));
+    let error = numbers.next().unwrap().unwrap_err();
+    assert_eq!(error.pos(), 1);
+    assert_eq!(
+        error.to_string(),
+        "at offset 1: expected `just(\",\")` or end of input, found \" \""
+    );
+}
+
+#[test]
+fn records_must_consume_input() {
+    let mut records = take_while(|c: char| c.is_ascii_digit()).parse_iter("12ab");
+    assert_eq!(records.next(), Some(Ok("12")));
+    let error = records.next().unwrap().unwrap_err();
+    assert_eq!(error.pos(), 2);
+    assert_eq!(records.next(), None);
+
+    let blank_lines = take_while(|c: char| c.is_ascii_digit())
+        .parse_iter("1\n\n2")
+        .separator(just("\n"));
+    assert_eq!(
+        blank_lines.collect::<Result<Vec<_>, _>>(),
+        Ok(vec!["1", "", "2"])
+    );
+}
+
+#[test]
+fn streamed_records_are_not_buffered() {
+    let text: String = (0..10_000).map(|n| format!("{n}\n")).collect();
+    let record =
+        generic::take_while(|c: char| c.is_ascii_digit()).try_map(|s: String| s.parse::<u64>());
+    let mut records = record
+        .parse_iter(Input::from(Reader::new(Cursor::new(text))))
+        .separator(just("\n"));
+    let mut sum = 0;
+    while let Some(n) = records.next() {
+        sum += n.unwrap();
+        assert!(records.input().stream().buffered() <= 12);
+    }
+    assert_eq!(sum, (0..10_000).sum());
+}
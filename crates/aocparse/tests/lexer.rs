@@ -0,0 +1,135 @@
+use aocparse::{
+    lexer::lexer,
+    pratt::{pratt, Assoc},
+    recursive::recursive,
+    select,
+    span::Span,
+    text::{ident, just, number, take_until, whitespace},
+    token::{self, Token},
+    Expected, Input, Parser,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+enum Tok<'a> {
+    Num(i64),
+    Ident(&'a str),
+    Op(char),
+    Open,
+    Close,
+    Arrow,
+}
+
+impl Token for Tok<'_> {}
+
+fn tokens(source: &str) -> Vec<(Tok<'_>, Span)> {
+    lexer()
+        .token(number(10).map(Tok::Num))
+        .token(ident().map(Tok::Ident))
+        .token(just("->").to(Tok::Arrow))
+        .token(just("-").to(Tok::Op('-')))
+        .token(just("+").to(Tok::Op('+')))
+        .token(just("*").to(Tok::Op('*')))
+        .token(just("(").to(Tok::Open))
+        .token(just(")").to(Tok::Close))
+        .skip(just("#").then(take_until("\n")))
+        .skip(whitespace())
+        .lex(source)
+        .unwrap()
+}
+
+#[test]
+fn longest_match_and_skipped_input() {
+    let lexed = tokens("a->b - 1 # comment\n");
+    let tokens: Vec<_> = lexed.iter().map(|(token, _)| token.clone()).collect();
+    assert_eq!(
+        tokens,
+        [
+            Tok::Ident("a"),
+            Tok::Arrow,
+            Tok::Ident("b"),
+            Tok::Op('-'),
+            Tok::Num(1),
+        ]
+    );
+    assert_eq!(lexed[1].1, Span::new(1, 3));
+    assert_eq!(lexed[4].1.range(), 7..8);
+}
+
+#[test]
+fn lexing_errors() {
+    let lexer = lexer().token(number::<u32>(10)).skip(just(" "));
+    let error = lexer.lex("1 2 x").unwrap_err();
+    assert_eq!(error.pos(), 4);
+    assert_eq!(error.found(), Some("x"));
+    assert!(error.expected().any(|e| *e == Expected::Just(" ")));
+    assert_eq!(lexer.lex(""), Ok(vec![]));
+}
+
+fn expr<'a>() -> impl Parser<'a, &'a [Tok<'a>], i64> {
+    recursive(|expr| {
+        let atom = select! {
+            Tok::Num(n) => n,
+            Tok::Ident(name) => name.len() as i64 * 10,
+        }
+        .labelled("an operand")
+        .or(token::just(Tok::Open)
+            .then(expr)
+            .then(token::just(Tok::Close))
+            .map(|((_, e), _)| e));
+
+        pratt(atom)
+            .infix(token::just(Tok::Op('+')), Assoc::Left, 1, |a, b| a + b)
+            .infix(token::just(Tok::Op('-')), Assoc::Left, 1, |a, b| a - b)
+            .infix(token::just(Tok::Op('*')), Assoc::Left, 2, |a, b| a * b)
+    })
+}
+
+#[test]
+fn parse_tokens() {
+    let lexed = tokens("2 * (x + 3) - y");
+    let tokens: Vec<_> = lexed.iter().map(|(token, _)| token.clone()).collect();
+    let mut input = Input::from(&tokens[..]);
+    assert_eq!(expr().parse_complete(&mut input), Ok(2 * (10 + 3) - 10));
+}
+
+#[test]
+fn errors_map_back_to_spans() {
+    // errors are reported at token indices, which map back to source spans
+    let lexed = tokens("(1 + ) * 2");
+    let tokens: Vec<_> = lexed.iter().map(|(token, _)| token.clone()).collect();
+    let error = expr()
+        .parse_complete(&mut Input::from(&tokens[..]))
+        .unwrap_err();
+    assert_eq!(error.pos(), 3);
+    assert_eq!(error.found(), Some("Close"));
+    assert!(error
+        .expected()
+        .any(|e| *e == Expected::Label("an operand")));
+    assert_eq!(lexed[error.pos()].1, Span::new(5, 6));
+}
+
+#[test]
+fn token_primitives() {
+    let tokens = [Tok::Num(1), Tok::Op('+'), Tok::Num(2)];
+    let numbers = select! { Tok::Num(n) if n > 0 => n }.separated_by(token::any());
+    assert_eq!(
+        numbers.parse_complete(&mut Input::from(&tokens[..])),
+        Ok(vec![1, 2])
+    );
+
+    let error = token::just(Tok::Arrow)
+        .parse(&mut Input::from(&tokens[..]))
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "at offset 0: expected Arrow, found \"Num(1)\""
+    );
+
+    let error = token::any::<Tok>()
+        .repeated()
+        .exactly(4)
+        .parse(&mut Input::from(&tokens[..]))
+        .unwrap_err();
+    assert_eq!(error.pos(), 3);
+    assert_eq!(error.found(), None);
+}
@@ -0,0 +1,53 @@
+use aocparse::{
+    primitive::end,
+    text::{just, number, one_of},
+    Input, Parser,
+};
+
+const DIGITS: &[&str] = &[
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// Day 1 part 2, where spelled-out digits may overlap.
+#[test]
+fn overlapping_words() {
+    let digit = one_of(DIGITS).map(|word| DIGITS.iter().position(|d| *d == word).unwrap() + 1);
+    let letter = one_of(&[
+        "a", "b", "c", "d", "e", "f", "g", "h", "i", "j", "k", "l", "m", "n", "o", "p", "q", "r",
+        "s", "t", "u", "v", "w", "x", "y", "z",
+    ]);
+    let scan = digit
+        .rewind()
+        .then(letter.clone())
+        .map(|(d, _)| Some(d))
+        .or(letter.to(None))
+        .repeated();
+
+    let digits: Vec<_> = scan.parse_str_all("eightwothree").unwrap();
+    let digits: Vec<_> = digits.into_iter().flatten().collect();
+    assert_eq!(digits, [8, 2, 3]);
+}
+
+#[test]
+fn lookahead_never_moves_position() {
+    let mut input = Input::from("abc");
+    assert!(just("ab").rewind().parse(&mut input).is_ok());
+    assert!(just("x").rewind().parse(&mut input).is_err());
+    assert!(just("ab").not().parse(&mut input).is_err());
+    assert!(just("x").not().parse(&mut input).is_ok());
+    assert!(just("abc").and_is(just("ab")).parse(&mut input).is_ok());
+    assert!(just("abc").parse_complete(&mut input).is_err());
+}
+
+#[test]
+fn and_is_and_not() {
+    let parser = number::<u32>(10).and_is(just("12")).then(just("x").not());
+    assert_eq!(parser.parse_str("124"), Ok((124, ())));
+    let error = parser.parse_str("124x").unwrap_err();
+    assert_eq!(error.pos(), 3);
+    assert_eq!(error.message(), Some("unexpected \"x\""));
+    assert!(parser.parse_str("224").is_err());
+
+    let error = end().not().parse_str("").unwrap_err();
+    assert_eq!(error.message(), Some("unexpected end of input"));
+}
@@ -0,0 +1,55 @@
+use std::cell::Cell;
+
+use aocparse::{
+    recursive::recursive,
+    text::{just, number},
+    Parser,
+};
+
+#[test]
+fn left_recursion() {
+    let expr = recursive(|expr| {
+        let term = recursive(|term| {
+            term.then(just("*"))
+                .then(number::<i64>(10))
+                .map(|((a, _), b)| a * b)
+                .or(number(10))
+                .memoized()
+        });
+        expr.clone()
+            .then(just("-"))
+            .then(term.clone())
+            .map(|((a, _), b)| a - b)
+            .or(expr
+                .then(just("+"))
+                .then(term.clone())
+                .map(|((a, _), b)| a + b))
+            .or(term)
+            .memoized()
+    });
+
+    assert_eq!(expr.parse_str("10-4-3"), Ok(3));
+    assert_eq!(expr.parse_str("2*3-4*5+6"), Ok(-8));
+    assert_eq!(expr.parse_str("7"), Ok(7));
+    assert!(expr.parse_str("*1").is_err());
+}
+
+#[test]
+fn applies_child_once_per_position() {
+    let calls = Cell::new(0);
+    let counted = number::<u32>(10)
+        .map(|n| {
+            calls.set(calls.get() + 1);
+            n
+        })
+        .memoized();
+    let parser = counted
+        .clone()
+        .then(just("a"))
+        .map(|(n, _)| n)
+        .or(counted.clone().then(just("b")).map(|(n, _)| n))
+        .or(counted);
+
+    assert_eq!(parser.parse_str("12c"), Ok(12));
+    assert_eq!(calls.get(), 1);
+}
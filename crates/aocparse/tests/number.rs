@@ -0,0 +1,83 @@
+use aocparse::{
+    text::{float, number},
+    Parser,
+};
+
+#[test]
+fn integers_in_every_radix() {
+    assert_eq!(number::<u32>(2).parse_prefix("1012"), Ok((5, "2")));
+    assert_eq!(number::<u32>(16).parse_str("fF"), Ok(255));
+    assert_eq!(number::<u64>(36).parse_str("zz"), Ok(36 * 36 - 1));
+    assert_eq!(number::<i16>(8).parse_str("-17"), Ok(-15));
+    assert!(number::<u8>(10).parse_str("x").is_err());
+}
+
+#[test]
+#[should_panic = "radix must be between 2 and 36"]
+fn radix_is_checked_when_building_the_parser() {
+    let _ = number::<u8>(40);
+}
+
+#[test]
+fn overflow_is_an_error() {
+    assert_eq!(number::<u8>(10).parse_str("255"), Ok(255));
+    let error = number::<u8>(10).parse_str("256").unwrap_err();
+    assert_eq!(error.pos(), 0);
+    assert_eq!(error.message(), Some("number does not fit in u8"));
+
+    assert_eq!(number::<i8>(10).parse_str("-128"), Ok(i8::MIN));
+    assert!(number::<i8>(10).parse_str("128").is_err());
+    assert_eq!(
+        number::<i128>(10).parse_str(&i128::MIN.to_string()),
+        Ok(i128::MIN)
+    );
+    assert_eq!(
+        number::<usize>(10).parse_str(&usize::MAX.to_string()),
+        Ok(usize::MAX)
+    );
+}
+
+#[test]
+fn signs() {
+    assert_eq!(number::<i32>(10).parse_str("+42"), Ok(42));
+    assert_eq!(number::<isize>(10).parse_str("-42"), Ok(-42));
+    assert!(number::<i32>(10).parse_str("-").is_err());
+    let error = number::<i32>(10).parse_str("-x").unwrap_err();
+    assert_eq!(error.pos(), 1);
+    assert_eq!(error.found(), Some("x"));
+    assert!(number::<i32>(10).parse_str("").is_err());
+    assert!(number::<u32>(10).parse_str("-1").is_err());
+}
+
+#[test]
+fn prefixes_and_separators() {
+    let prefixed = number::<u16>(10).allow_prefix();
+    assert_eq!(prefixed.parse_str("0x1F"), Ok(31));
+    assert_eq!(prefixed.parse_str("0o17"), Ok(15));
+    assert_eq!(prefixed.parse_str("0b101"), Ok(5));
+    assert_eq!(prefixed.parse_str("12"), Ok(12));
+    assert_eq!(prefixed.parse_prefix("0xg"), Ok((0, "xg")));
+    assert_eq!(number::<u16>(10).parse_prefix("0x1F"), Ok((0, "x1F")));
+
+    let separated = number::<u32>(10).allow_separators();
+    assert_eq!(separated.parse_str("1_000_000"), Ok(1_000_000));
+    assert_eq!(separated.parse_prefix("1_"), Ok((1, "_")));
+    assert!(separated.parse_str("_1").is_err());
+    assert_eq!(number::<u32>(10).parse_prefix("1_0"), Ok((1, "_0")));
+}
+
+#[test]
+fn floats() {
+    assert_eq!(float::<f64>().parse_str("1.5"), Ok(1.5));
+    assert_eq!(float::<f64>().parse_str("-.5"), Ok(-0.5));
+    assert_eq!(float::<f64>().parse_str("+3"), Ok(3.0));
+    assert_eq!(float::<f64>().parse_str("2.5E-2"), Ok(0.025));
+    assert_eq!(float::<f32>().parse_prefix("1e"), Ok((1.0, "e")));
+    assert_eq!(float::<f32>().parse_prefix("1.e3x"), Ok((1000.0, "x")));
+    assert_eq!(
+        float::<f64>().allow_separators().parse_str("1_000.25"),
+        Ok(1000.25)
+    );
+    assert!(float::<f64>().parse_str(".").is_err());
+    assert!(float::<f64>().parse_str("-e1").is_err());
+}
@@ -0,0 +1,89 @@
+use std::rc::Rc;
+
+use aocparse::{
+    span::Span,
+    text::{alpha, just, number, whitespace},
+    Parser,
+};
+
+fn assert_send_sync<'a, O, P>(_: &P)
+where
+    P: Parser<'a, &'a str, O> + Send + Sync,
+{
+}
+
+#[test]
+fn combinators_are_send_and_sync() {
+    let parser = just("a")
+        .map(Rc::new)
+        .repeated()
+        .separated_by(just(","))
+        .then(number::<u8>(10).optional())
+        .not();
+    assert_send_sync(&parser);
+    let parser = just("a")
+        .map(|()| Rc::new(1))
+        .repeated()
+        .foldl(|a, b| Rc::new(*a + *b))
+        .and_is(just("b").map(Rc::new));
+    assert_send_sync(&parser);
+}
+
+#[test]
+fn outputs_are_in_input_order() {
+    let input: String = (0..10_000).map(|n| format!("{n} x{}\n", n % 7)).collect();
+    let record = number::<u32>(10)
+        .then(whitespace())
+        .then(just("x"))
+        .then(number::<u32>(10))
+        .map(|(((n, _), _), m)| (n, m));
+    let records = record.parse_parallel(&input, "\n").unwrap();
+    assert_eq!(records.len(), 10_000);
+    assert!(records
+        .iter()
+        .enumerate()
+        .all(|(i, &(n, m))| n == i as u32 && m == n % 7));
+
+    assert_eq!(record.parse_parallel("", "\n"), Ok(vec![]));
+    assert_eq!(record.parse_parallel("1 x2", "\n"), Ok(vec![(1, 2)]));
+}
+
+#[test]
+fn errors_have_global_positions() {
+    let words = alpha().repeated().at_least(1);
+    let input = "ab;cd;e1;f2";
+    let error = words.parse_parallel(input, ";").unwrap_err();
+    assert_eq!(error.pos(), 7);
+    assert_eq!(error.found(), Some("1"));
+
+    let input: String = (0..1000).map(|n| format!("{n},{n}\n")).collect();
+    let input = input.replace("\n500,500\n", "\n500,\n");
+    let pairs = number::<u32>(10).then(just(",")).then(number::<u32>(10));
+    let error = pairs.parse_parallel(&input, "\n").unwrap_err();
+    assert_eq!(&input[error.pos() - 4..error.pos()], "500,");
+    assert_eq!(error.found(), Some("\n"));
+}
+
+#[test]
+fn spans_have_global_positions() {
+    let numbers = number::<u32>(10).map_with_span(|n, span| (n, span));
+    assert_eq!(
+        numbers.parse_parallel("1\n22\n333", "\n"),
+        Ok(vec![
+            (1, Span::new(0, 1)),
+            (22, Span::new(2, 4)),
+            (333, Span::new(5, 8))
+        ])
+    );
+}
+
+#[test]
+fn multi_character_delimiters() {
+    let groups = number::<u32>(10)
+        .separated_by(just("\n"))
+        .map(|group| group.into_iter().sum::<u32>());
+    assert_eq!(
+        groups.parse_parallel("1\n2\n\n3\n\n4\n5\n\n", "\n\n"),
+        Ok(vec![3, 3, 9])
+    );
+}
@@ -0,0 +1,45 @@
+use aocparse::{
+    pratt::{pratt, Assoc},
+    recursive::recursive,
+    text::{just, number},
+    Parser,
+};
+
+/// AoC 2020 day 18, where `+` and `*` are parsed with custom precedence.
+#[test]
+fn custom_precedence() {
+    let parser = |add: u16, mul: u16| {
+        recursive(move |expr| {
+            let atom =
+                number::<u64>(10).or(just("(").then(expr).then(just(")")).map(|((_, e), _)| e));
+            pratt(atom)
+                .infix(just(" + "), Assoc::Left, add, |a, b| a + b)
+                .infix(just(" * "), Assoc::Left, mul, |a, b| a * b)
+        })
+    };
+
+    let same = parser(1, 1);
+    assert_eq!(same.parse_str("1 + 2 * 3 + 4 * 5 + 6"), Ok(71));
+    assert_eq!(same.parse_str("2 * 3 + (4 * 5)"), Ok(26));
+
+    let inverted = parser(2, 1);
+    assert_eq!(inverted.parse_str("1 + 2 * 3 + 4 * 5 + 6"), Ok(231));
+    assert_eq!(
+        inverted.parse_str("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2"),
+        Ok(23340)
+    );
+}
+
+#[test]
+fn associativity_and_unary_operators() {
+    let expr = pratt(number::<u32>(10).map(|n| n as i64))
+        .prefix(just("-"), 3, |a| -a)
+        .postfix(just("!"), 4, |a| (1..=a).product())
+        .infix(just("-"), Assoc::Left, 1, |a, b| a - b)
+        .infix(just("^"), Assoc::Right, 2, |a, b| a.pow(b as u32));
+
+    assert_eq!(expr.parse_str("10-4-3"), Ok(3));
+    assert_eq!(expr.parse_str("2^3^2"), Ok(512));
+    assert_eq!(expr.parse_str("-3!"), Ok(-6));
+    assert_eq!(expr.parse_str("3!-2^2"), Ok(2));
+}
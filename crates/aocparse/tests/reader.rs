@@ -0,0 +1,135 @@
+use std::io::{self, BufRead, BufReader, Cursor, Read};
+
+use aocparse::{
+    primitive::end,
+    reader::Reader,
+    text::generic::{alpha, just, take_while, whitespace},
+    Input, Parser,
+};
+
+/// A reader producing the numbers from `0` up to a limit, one per line.
+struct Numbers {
+    next: u32,
+    limit: u32,
+    pending: Vec<u8>,
+}
+
+impl Read for Numbers {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.pending.is_empty() && self.next < self.limit {
+            self.pending = format!("{}\n", self.next).into_bytes();
+            self.next += 1;
+        }
+        let len = buf.len().min(self.pending.len());
+        buf[..len].copy_from_slice(&self.pending[..len]);
+        self.pending.drain(..len);
+        Ok(len)
+    }
+}
+
+/// A reader producing some text and then failing.
+struct Failing<'a> {
+    text: &'a [u8],
+}
+
+impl Read for Failing<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.text.is_empty() {
+            return Err(io::Error::other("disk on fire"));
+        }
+        let len = buf.len().min(self.text.len());
+        buf[..len].copy_from_slice(&self.text[..len]);
+        self.text = &self.text[len..];
+        Ok(len)
+    }
+}
+
+fn line<'a, R: BufRead + 'a>() -> impl Parser<'a, Reader<R>, u32> {
+    take_while(|c: char| c.is_ascii_digit())
+        .try_map(|digits: String| digits.parse::<u32>())
+        .then(just("\n"))
+        .map(|(n, _)| n)
+}
+
+#[test]
+fn large_input_is_not_buffered() {
+    let numbers = Numbers {
+        next: 0,
+        limit: 100_000,
+        pending: Vec::new(),
+    };
+    let mut input = Input::from(Reader::new(BufReader::new(numbers)));
+    let line = line();
+    let mut sum = 0u64;
+    let mut max_buffered = 0;
+    while end().parse(&mut input).is_err() {
+        sum += u64::from(line.parse(&mut input).unwrap());
+        max_buffered = max_buffered.max(input.stream().buffered());
+    }
+    assert_eq!(sum, (0..100_000u64).sum());
+    assert_eq!(input.pos(), 588_890);
+    assert!(max_buffered <= 12, "buffered {max_buffered} bytes");
+}
+
+#[test]
+fn backtracking_across_lines() {
+    let parser = just("ab\ncd").or(just("ab\nce")).then(just("\nf"));
+    let mut input = Input::from(Reader::new(Cursor::new("ab\nce\nf")));
+    assert_eq!(parser.parse(&mut input), Ok(((), ())));
+    assert!(end().parse(&mut input).is_ok());
+
+    let words = alpha()
+        .repeated()
+        .at_least(1)
+        .map(|letters: Vec<String>| letters.concat())
+        .separated_by(whitespace());
+    let mut input = Input::from(Reader::new(Cursor::new("some\nwords  across\n\nlines")));
+    assert_eq!(
+        words.parse(&mut input),
+        Ok(vec![
+            "some".to_string(),
+            "words".to_string(),
+            "across".to_string(),
+            "lines".to_string()
+        ])
+    );
+}
+
+#[test]
+fn errors_report_byte_positions() {
+    let mut input = Input::from(Reader::new(Cursor::new("12\n3x\n")));
+    let line = line();
+    assert_eq!(line.parse(&mut input), Ok(12));
+    let error = line.parse(&mut input).unwrap_err();
+    assert_eq!(error.pos(), 4);
+    assert_eq!(error.found(), Some("x"));
+    assert_eq!(input.pos(), 3);
+}
+
+#[test]
+fn io_errors_end_the_stream() {
+    let reader = BufReader::new(Failing { text: b"1\n2" });
+    let mut input = Input::from(Reader::new(reader));
+    let line = line();
+    assert_eq!(line.parse(&mut input), Ok(1));
+    let error = line.parse(&mut input).unwrap_err();
+    assert_eq!(error.pos(), 3);
+    assert_eq!(error.found(), None);
+
+    let io_error = input.stream().take_error().unwrap();
+    assert_eq!(io_error.to_string(), "disk on fire");
+    assert!(input.stream().take_error().is_none());
+}
+
+#[test]
+fn multibyte_characters() {
+    let reader = BufReader::with_capacity(1, Cursor::new("héllo\nwörld\n"));
+    let mut input = Input::from(Reader::new(reader));
+    let word = take_while(|c: char| c != '\n')
+        .then(just("\n"))
+        .map(|(w, _)| w);
+    assert_eq!(word.parse(&mut input), Ok("héllo".to_string()));
+    assert_eq!(input.pos(), 7);
+    assert_eq!(word.parse(&mut input), Ok("wörld".to_string()));
+    assert_eq!(input.pos(), 14);
+}
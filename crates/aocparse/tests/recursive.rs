@@ -0,0 +1,42 @@
+use aocparse::{
+    recursive::recursive,
+    text::{just, number},
+    Parser,
+};
+
+#[derive(Debug, PartialEq)]
+enum Packet {
+    Integer(u32),
+    List(Vec<Packet>),
+}
+
+#[test]
+fn nested_lists() {
+    let packet = recursive(|packet| {
+        let items = packet
+            .clone()
+            .then(just(",").then(packet).repeated())
+            .map(|(first, rest)| {
+                let mut items = vec![first];
+                items.extend(rest.into_iter().map(|(_, p)| p));
+                items
+            })
+            .optional()
+            .map(Option::unwrap_or_default);
+
+        number(10).map(Packet::Integer).or(just("[")
+            .then(items)
+            .then(just("]"))
+            .map(|((_, items), _)| Packet::List(items)))
+    });
+
+    assert_eq!(
+        packet.parse_str("[1,[2,[]],3]"),
+        Ok(Packet::List(vec![
+            Packet::Integer(1),
+            Packet::List(vec![Packet::Integer(2), Packet::List(vec![])]),
+            Packet::Integer(3),
+        ]))
+    );
+    assert!(packet.parse_str("[1,[2]").is_err());
+}
@@ -0,0 +1,71 @@
+use aocparse::{
+    text::{just, number, one_of, whitespace},
+    Input, Parser,
+};
+
+#[test]
+fn bounded_repetition() {
+    let bits = one_of(&["0", "1"]).repeated().exactly(3);
+    assert_eq!(bits.parse_str("1011"), Ok(vec!["1", "0", "1"]));
+    assert!(bits.parse_str("10").is_err());
+
+    let parser = just("a").repeated().at_least(2).at_most(3).then(just("b"));
+    assert!(parser.parse_str("ab").is_err());
+    assert!(parser.parse_str("aab").is_ok());
+    assert!(parser.parse_str("aaab").is_ok());
+    assert!(parser.parse_str("aaaab").is_err());
+
+    let error = number::<u8>(10)
+        .repeated()
+        .at_least(1)
+        .parse_str("x")
+        .unwrap_err();
+    assert_eq!(error.pos(), 0);
+}
+
+#[test]
+fn zero_width_iterations_stop() {
+    let parser = whitespace().repeated().then(just("x"));
+    assert_eq!(parser.parse_str("  x"), Ok((vec![vec![(), ()]], ())));
+    assert_eq!(parser.parse_str("x"), Ok((vec![], ())));
+}
+
+#[test]
+fn separated_by() {
+    let list = number::<u32>(10).separated_by(just(","));
+    assert_eq!(list.parse_str(""), Ok(vec![]));
+    assert_eq!(list.parse_str("1,2,3"), Ok(vec![1, 2, 3]));
+
+    // a trailing separator is left unconsumed unless allowed
+    let parser = list.clone().then(just(",;"));
+    assert_eq!(parser.parse_str("1,2,;"), Ok((vec![1, 2], ())));
+    let parser = list.clone().allow_trailing().then(just(";"));
+    assert_eq!(parser.parse_str("1,2,;"), Ok((vec![1, 2], ())));
+
+    let parser = list.clone().allow_leading();
+    assert_eq!(parser.parse_str(",1,2"), Ok(vec![1, 2]));
+    assert_eq!(list.parse_str(",1,2"), Ok(vec![]));
+
+    let parser = list.at_least(2).at_most(3);
+    assert!(parser.parse_str("1").is_err());
+    assert_eq!(parser.parse_str("1,2,3,4"), Ok(vec![1, 2, 3]));
+}
+
+#[test]
+fn foldl_folds_left_to_right() {
+    let difference = number::<i32>(10)
+        .separated_by(just("-"))
+        .foldl(|a, b| a - b);
+    assert_eq!(difference.parse_str("10-4-3"), Ok(3));
+
+    let error = difference.parse_str("x").unwrap_err();
+    assert_eq!(error.message(), Some("expected at least one item to fold"));
+
+    let sum = number::<i32>(10)
+        .separated_by(just(","))
+        .allow_leading()
+        .foldl(|a, b| a + b);
+    let mut input = Input::from(",x");
+    assert!(sum.parse(&mut input).is_err());
+    assert_eq!(input.pos(), 0);
+}
@@ -0,0 +1,126 @@
+use aocparse::{
+    boxed::BoxedParser,
+    combinator::{choice, seq},
+    text::{alpha, just, number, whitespace},
+    Expected, Parser,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+enum Instruction {
+    Noop,
+    Addx(i32),
+    Jump(String),
+}
+
+#[test]
+fn choice_over_tuples() {
+    let instruction = choice((
+        just("noop").to(Instruction::Noop),
+        just("addx ")
+            .ignore_then(number::<i32>(10))
+            .map(Instruction::Addx),
+        just("jmp ")
+            .ignore_then(alpha().repeated().at_least(1).to_slice())
+            .map(|label: &str| Instruction::Jump(label.to_string())),
+    ));
+    let program = instruction.separated_by(just("\n"));
+    assert_eq!(
+        program.parse_str_all("noop\naddx -5\njmp end"),
+        Ok(vec![
+            Instruction::Noop,
+            Instruction::Addx(-5),
+            Instruction::Jump("end".to_string())
+        ])
+    );
+
+    let error = program.parse_str_all("noop\nmul 2").unwrap_err();
+    assert_eq!(error.pos(), 5);
+    assert_eq!(error.found(), Some("m"));
+    let error = choice((just("noop"), just("addx")))
+        .parse_str("mul")
+        .unwrap_err();
+    assert_eq!(
+        error.expected().collect::<Vec<_>>(),
+        [&Expected::Just("addx"), &Expected::Just("noop")]
+    );
+}
+
+#[test]
+fn choice_respects_cuts() {
+    let parser = choice((just("a").then(just("b").cut()).to(1), just("a").to(2)));
+    assert_eq!(parser.parse_str("ab"), Ok(1));
+    let error = parser.parse_str("ac").unwrap_err();
+    assert!(error.is_committed());
+    assert_eq!(error.pos(), 1);
+
+    let parser = choice((just("x").to(0), parser));
+    assert!(parser.parse_str("ac").unwrap_err().is_committed());
+}
+
+#[test]
+fn choice_over_collections() {
+    let names = ["one", "two", "three", "four"];
+    let digits: Vec<BoxedParser<&str, usize>> = names
+        .iter()
+        .enumerate()
+        .map(|(i, &name)| just(name).to(i + 1).boxed())
+        .collect();
+    let digit = choice(digits).or(number::<usize>(10));
+    assert_eq!(
+        digit.repeated().parse_str_all("two3four"),
+        Ok(vec![2, 3, 4])
+    );
+
+    let empty: Vec<BoxedParser<&str, ()>> = Vec::new();
+    assert!(choice(empty).parse_str("a").is_err());
+
+    let signs = choice(["+", "-"].map(just));
+    assert_eq!(
+        signs.repeated().parse_str_all("+-+").map(|s| s.len()),
+        Ok(3)
+    );
+}
+
+#[test]
+fn sequences_produce_flat_tuples() {
+    let move_step = seq((
+        just("move "),
+        number::<u32>(10),
+        just(" from "),
+        number::<u32>(10),
+        just(" to "),
+        number::<u32>(10),
+    ))
+    .map(|(_, count, _, from, _, to)| (count, from, to));
+    assert_eq!(move_step.parse_str("move 3 from 1 to 2"), Ok((3, 1, 2)));
+
+    let error = move_step.parse_str("move 3 from x to 2").unwrap_err();
+    assert_eq!(error.pos(), 12);
+
+    assert_eq!(seq((just("a"),)).parse_str("a"), Ok(((),)));
+
+    let n = || number::<u8>(10).then_ignore(whitespace());
+    let twelve = seq((n(), n(), n(), n(), n(), n(), n(), n(), n(), n(), n(), n()));
+    assert_eq!(
+        twelve.parse_str("0 1 2 3 4 5 6 7 8 9 10 11"),
+        Ok((0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11))
+    );
+}
+
+#[test]
+fn ignoring_outputs() {
+    let coordinate = number::<i32>(10)
+        .then_ignore(just(","))
+        .then(number::<i32>(10))
+        .delimited_by(just("<"), just(">"));
+    let velocity = just("v=").ignore_then(coordinate.clone());
+    assert_eq!(velocity.parse_str("v=<-1,2>"), Ok((-1, 2)));
+    assert_eq!(
+        coordinate
+            .parse_str("<3,4")
+            .unwrap_err()
+            .expected()
+            .collect::<Vec<_>>(),
+        [&Expected::Just(">")]
+    );
+}
@@ -0,0 +1,66 @@
+use std::io::Cursor;
+
+use aocparse::{
+    bits::{bit, bits, Bits},
+    bytes::{byte, take},
+    reader::Reader,
+    text::{alpha, digit, generic, just, number, whitespace},
+    token::{any, Token},
+    Input, Parser,
+};
+
+#[test]
+fn slices_borrow_the_input() {
+    let source = String::from("move 12 from 3 to 4");
+    let step = alpha()
+        .repeated()
+        .at_least(1)
+        .then(whitespace())
+        .then(number::<u32>(10))
+        .to_slice();
+    let steps = step.separated_by(just(" "));
+    let slices = steps.parse_str_all(&source).unwrap();
+    assert_eq!(slices, ["move 12", "from 3", "to 4"]);
+    assert!(slices
+        .iter()
+        .all(|slice| source.as_bytes().as_ptr_range().contains(&slice.as_ptr())));
+
+    let error = digit(10)
+        .then(alpha())
+        .to_slice()
+        .parse_str("12")
+        .unwrap_err();
+    assert_eq!(error.pos(), 1);
+    assert_eq!(just("a").optional().to_slice().parse_str("b"), Ok(""));
+}
+
+#[test]
+fn slices_of_other_streams() {
+    let header = byte(b'P').then(take(1)).to_slice();
+    assert_eq!(header.parse_bytes(b"P6\n"), Ok(&b"P6"[..]));
+
+    let packet = bits::<u8>(3).then(bit()).to_slice();
+    let slice = packet
+        .parse(&mut Input::from(Bits::new(&[0b1101_0000])))
+        .unwrap();
+    assert_eq!(slice.len(), 4);
+    assert_eq!(bits::<u8>(4).parse(&mut Input::from(slice)), Ok(0b1101));
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Word(&'static str);
+    impl Token for Word {}
+    let tokens = [Word("a"), Word("b"), Word("c")];
+    let pair = any().then(any()).to_slice();
+    assert_eq!(pair.parse(&mut Input::from(&tokens[..])), Ok(&tokens[..2]));
+
+    let mut input = Input::from(Reader::new(Cursor::new("ab\ncd\n")));
+    let lines = generic::alpha()
+        .repeated()
+        .then(just("\n"))
+        .to_slice()
+        .repeated();
+    assert_eq!(
+        lines.parse(&mut input),
+        Ok(vec!["ab\n".to_string(), "cd\n".to_string()])
+    );
+}
@@ -0,0 +1,109 @@
+use aocparse::{
+    bytes::byte,
+    span::{Span, Spanned},
+    text::{char, filter, just, number},
+    Error, Parser,
+};
+
+/// An item in an engine schematic from AoC 2023 day 3.
+#[derive(Clone, Debug, PartialEq)]
+enum Item {
+    Number(u32),
+    Symbol(char),
+}
+
+const SCHEMATIC: &str = "\
+467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.";
+
+#[test]
+fn part_numbers_from_spans() {
+    let number = number::<u32>(10).map(Item::Number);
+    let symbol = filter(|c: char| c != '.' && c != '\n')
+        .map(|s: &str| Item::Symbol(s.chars().next().unwrap()));
+    let item = number.or(symbol).spanned().map(Some);
+    let blank = char('.').or(char('\n')).to(None);
+    let items: Vec<Spanned<Item>> = item
+        .or(blank)
+        .repeated()
+        .parse_str_all(SCHEMATIC)
+        .unwrap()
+        .into_iter()
+        .flatten()
+        .collect();
+
+    let symbols: Vec<_> = items
+        .iter()
+        .filter(|item| matches!(item.value(), Item::Symbol(_)))
+        .map(|item| item.span().start_line_col(SCHEMATIC))
+        .collect();
+    let sum: u32 = items
+        .iter()
+        .filter_map(|item| match *item.value() {
+            Item::Number(n) => Some((n, item.span())),
+            Item::Symbol(_) => None,
+        })
+        .filter(|(_, span)| {
+            let (line, start) = span.start_line_col(SCHEMATIC);
+            let (_, end) = span.end_line_col(SCHEMATIC);
+            symbols
+                .iter()
+                .any(|&(l, c)| l.abs_diff(line) <= 1 && c + 1 >= start && c <= end)
+        })
+        .map(|(n, _)| n)
+        .sum();
+    assert_eq!(sum, 467 + 35 + 633 + 617);
+    assert_eq!(symbols, [(2, 4), (4, 7), (5, 4), (6, 6)]);
+
+    let first = &items[0];
+    assert_eq!(first.value(), &Item::Number(467));
+    assert_eq!(first.span(), Span::new(0, 3));
+    assert_eq!(first.span().end_line_col(SCHEMATIC), (1, 4));
+}
+
+#[test]
+fn spans_for_semantic_errors() {
+    let range = number::<u32>(10)
+        .then(just("-"))
+        .then(number::<u32>(10))
+        .map_with_span(|((start, _), end), span: Span| (start, end, span));
+    let source = "ok 1-2\nbad 9-3";
+    let (_, _, span) = just("ok ")
+        .then(range.clone())
+        .then(just("\nbad "))
+        .then(range)
+        .map(|((_, _), r)| r)
+        .parse_str(source)
+        .unwrap();
+    let error = Error::custom(span.start(), "range is reversed", None);
+    let report = error.report(source).to_string();
+    assert!(report.contains(" --> 2:5"), "{report}");
+    assert_eq!(span.range(), 11..14);
+    assert_eq!(&source[span.range()], "9-3");
+}
+
+#[test]
+fn spans_of_other_streams() {
+    let parser = byte(b'a').then(byte(b'b').map_with_span(|b, span: Span| (b, span)));
+    assert_eq!(
+        parser.parse_bytes(b"ab"),
+        Ok((b'a', (b'b', Span::new(1, 2))))
+    );
+
+    let spanned = Spanned::new("x", Span::new(3, 4)).map(str::len);
+    assert_eq!(spanned.into_inner(), 1);
+}
+
+#[test]
+fn line_col_of_multibyte_text() {
+    let source = "héllo\nwörld";
+    let span = Span::new(10, 12);
+    assert_eq!(&source[span.range()], "rl");
+    assert_eq!(span.start_line_col(source), (2, 3));
+    assert_eq!(span.end_line_col(source), (2, 5));
+    assert_eq!(Span::new(100, 200).start_line_col(source), (2, 6));
+}
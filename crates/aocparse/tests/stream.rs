@@ -0,0 +1,136 @@
+use std::cell::RefCell;
+
+use aocparse::{
+    bits::Bits,
+    pratt::{pratt, Assoc},
+    recursive::recursive,
+    span::Span,
+    text::generic::{alpha, digit, ident, just, one_of, take_until, whitespace},
+    Error, Input, Parser, Source, Stream,
+};
+
+/// A string stream that checks that saved positions are released in reverse order.
+struct Tracked<'a> {
+    text: &'a str,
+    saved: RefCell<Vec<usize>>,
+}
+
+impl Source for Tracked<'_> {
+    fn found(&self, pos: usize) -> Option<String> {
+        self.text.found(pos)
+    }
+}
+
+impl<'a> Stream for Tracked<'a> {
+    type Item = char;
+    type Slice = &'a str;
+    type Span = Span;
+
+    fn next(&self, pos: usize) -> Option<(char, usize)> {
+        self.text.next(pos)
+    }
+
+    fn slice(&self, start: usize, end: usize) -> &'a str {
+        self.text.slice(start, end)
+    }
+
+    fn span(&self, start: usize, end: usize) -> Span {
+        self.text.span(start, end)
+    }
+
+    fn save(&self, pos: usize) {
+        self.saved.borrow_mut().push(pos);
+    }
+
+    fn release(&self, pos: usize) {
+        assert_eq!(self.saved.borrow_mut().pop(), Some(pos));
+    }
+}
+
+/// Parse the whole text, checking that every saved position was released.
+fn parse_tracked<'a, P, O>(parser: &P, text: &'a str) -> Result<O, Error>
+where
+    P: Parser<'a, Tracked<'a>, O>,
+{
+    let mut input = Input::from(Tracked {
+        text,
+        saved: RefCell::new(Vec::new()),
+    });
+    let result = parser.parse_complete(&mut input);
+    assert_eq!(*input.stream().saved.borrow(), []);
+    result
+}
+
+#[test]
+fn text_primitives_on_custom_streams() {
+    let assignment = ident()
+        .then(whitespace())
+        .then(just("="))
+        .then(whitespace())
+        .then(digit(10).repeated().at_least(1))
+        .map(|((((name, _), _), _), digits)| (name, digits.concat()));
+    assert_eq!(
+        parse_tracked(&assignment, "x1 = 42"),
+        Ok(("x1", "42".to_string()))
+    );
+    assert_eq!(parse_tracked(&assignment, "x1 = ").unwrap_err().pos(), 5);
+
+    let until = take_until("->").then(one_of(&["->", "-"]));
+    assert_eq!(parse_tracked(&until, "ab->"), Ok(("ab", "->")));
+    assert!(parse_tracked(&until, "ab-").is_err());
+}
+
+#[test]
+fn saves_are_balanced() {
+    let expr = recursive(|expr| {
+        let atom = digit(10)
+            .repeated()
+            .at_least(1)
+            .map(|digits: Vec<&str>| digits.concat().parse::<i64>().unwrap())
+            .or(just("(").then(expr).then(just(")")).map(|((_, e), _)| e));
+        pratt(atom)
+            .prefix(just("-"), 3, |a| -a)
+            .infix(just("+"), Assoc::Left, 1, |a, b| a + b)
+            .infix(just("*"), Assoc::Left, 2, |a, b| a * b)
+            .postfix(just("!"), 4, |a| (1..=a).product())
+            .memoized()
+    });
+    assert_eq!(parse_tracked(&expr, "-(1+2)*3!+4"), Ok(-14));
+    assert!(parse_tracked(&expr, "(1+2").is_err());
+
+    let words = alpha()
+        .repeated()
+        .at_least(1)
+        .and_is(just("a").not().rewind())
+        .labelled("word")
+        .separated_by(just(",").cut())
+        .allow_trailing()
+        .optional();
+    assert_eq!(
+        parse_tracked(&words, "bc,d,").map(|w| w.map(|w| w.len())),
+        Ok(Some(2))
+    );
+    assert!(parse_tracked(&words, "bc,ad").is_err());
+}
+
+#[test]
+fn built_in_streams() {
+    let text = "é1";
+    assert_eq!(text.next(0), Some(('é', 2)));
+    assert_eq!(text.next(2), Some(('1', 3)));
+    assert_eq!(text.next(3), None);
+    assert_eq!(text.slice(0, 2), "é");
+    assert_eq!(text.span(0, 3), Span::new(0, 3));
+
+    let bytes: &[u8] = b"ab";
+    assert_eq!(bytes.next(1), Some((b'b', 2)));
+    assert_eq!(bytes.slice(0, 1), b"a");
+
+    let bits = Bits::new(&[0b0110_0000, 0xff]);
+    let slice = bits.slice(1, 10);
+    assert_eq!(slice.len(), 9);
+    assert_eq!(slice.next(0), Some((true, 1)));
+    assert_eq!(slice.next(2), Some((false, 3)));
+    assert_eq!(slice.next(8), Some((true, 9)));
+    assert_eq!(slice.next(9), None);
+}
@@ -0,0 +1,55 @@
+use aocparse::{
+    text::{alpha, alphanumeric, any, ascii, char, digit, ident, none_of, take_until, take_while},
+    Expected, Parser,
+};
+
+#[test]
+fn single_characters_respect_utf8_boundaries() {
+    assert_eq!(any().parse_prefix("ßx"), Ok(("ß", "x")));
+    assert_eq!(char('→').parse_prefix("→1"), Ok(("→", "1")));
+    assert_eq!(alpha().parse_prefix("éa"), Ok(("é", "a")));
+    assert_eq!(alphanumeric().parse_prefix("٣x"), Ok(("٣", "x")));
+    assert_eq!(none_of(",;").parse_prefix("ü,"), Ok(("ü", ",")));
+    assert!(none_of(",;").parse_str(";").is_err());
+    assert!(any().parse_str("").is_err());
+
+    let error = char('x').parse_str("ñ").unwrap_err();
+    assert_eq!(error.expected().collect::<Vec<_>>(), [&Expected::Char('x')]);
+    assert_eq!(error.found(), Some("ñ"));
+}
+
+#[test]
+fn digits_in_radix() {
+    assert_eq!(
+        digit(16).repeated().parse_str_all("fF09"),
+        Ok(vec!["f", "F", "0", "9"])
+    );
+    assert!(digit(8).parse_str("8").is_err());
+}
+
+#[test]
+#[should_panic = "radix must be between 2 and 36"]
+fn digits_in_radix_one() {
+    let _ = digit(1).parse_str("0");
+}
+
+#[test]
+fn runs_of_characters() {
+    assert_eq!(ident().parse_prefix("_foo1 bar"), Ok(("_foo1", " bar")));
+    assert!(ident().parse_str("1foo").is_err());
+
+    assert_eq!(ascii().parse_prefix("abc→"), Ok(("abc", "→")));
+    assert!(ascii().parse_str("→").is_err());
+
+    assert_eq!(
+        take_while(|c| c != ':').parse_prefix("ab:c"),
+        Ok(("ab", ":c"))
+    );
+    assert_eq!(
+        take_while(char::is_numeric).parse_prefix("x"),
+        Ok(("", "x"))
+    );
+
+    assert_eq!(take_until("->").parse_prefix("a→b->c"), Ok(("a→b", "->c")));
+    assert!(take_until("->").parse_str("abc").is_err());
+}
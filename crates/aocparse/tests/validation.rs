@@ -0,0 +1,102 @@
+use aocparse::{
+    recursive::recursive,
+    text::{just, number, one_of},
+    Input, Parser,
+};
+
+#[derive(Debug, PartialEq)]
+enum Colour {
+    Red,
+    Green,
+    Blue,
+}
+
+#[test]
+fn try_map_reports_custom_errors() {
+    let colour = one_of(&["red", "green", "blue", "purple"]).try_map(|c| match c {
+        "red" => Ok(Colour::Red),
+        "green" => Ok(Colour::Green),
+        "blue" => Ok(Colour::Blue),
+        _ => Err(format!("colour must be red, green or blue, not {}", c)),
+    });
+    let draw = number::<u32>(10).then(just(" ")).then(colour);
+
+    assert_eq!(draw.parse_str("3 blue"), Ok(((3, ()), Colour::Blue)));
+
+    let error = draw.parse_str("3 purple").unwrap_err();
+    assert_eq!(error.pos(), 2);
+    assert_eq!(
+        error.to_string(),
+        "at offset 2: colour must be red, green or blue, not purple"
+    );
+}
+
+#[test]
+fn filter_rewinds_and_allows_alternatives() {
+    let small = number::<u32>(10).filter(|n| *n < 256).map(Some);
+    let parser = small.clone().or(number::<u32>(10).to(None));
+
+    assert_eq!(parser.parse_str("12"), Ok(Some(12)));
+    assert_eq!(parser.parse_str("1024"), Ok(None));
+
+    let error = small.parse_str("1024").unwrap_err();
+    assert_eq!(error.pos(), 0);
+    assert_eq!(error.message(), Some("value did not satisfy filter"));
+    assert_eq!(error.found(), Some("1"));
+}
+
+#[test]
+fn validate_discards_diagnostics_when_backtracking() {
+    let byte = number::<u32>(10).validate(|n| match *n {
+        0..=255 => Ok(()),
+        _ => Err("number must be < 256"),
+    });
+    let parser = byte
+        .clone()
+        .then(just(";"))
+        .map(|(n, _)| n)
+        .or(byte.then(just(",")).map(|(n, _)| n));
+
+    let mut input = Input::from("300,");
+    assert_eq!(parser.parse(&mut input), Ok(300));
+    assert_eq!(input.diagnostics().len(), 1);
+    assert_eq!(input.diagnostics()[0].pos(), 0);
+    assert_eq!(
+        input.diagnostics()[0].message(),
+        Some("number must be < 256")
+    );
+}
+
+#[test]
+fn memoized_rules_replay_diagnostics() {
+    let byte = number::<u32>(10)
+        .validate(|n| if *n < 256 { Ok(()) } else { Err("big") })
+        .memoized();
+    let parser = byte
+        .clone()
+        .then(just(";"))
+        .map(|(n, _)| n)
+        .or(byte.then(just(",")).map(|(n, _)| n));
+
+    let mut input = Input::from("300,");
+    assert_eq!(parser.parse(&mut input), Ok(300));
+    assert_eq!(input.diagnostics().len(), 1);
+    assert_eq!(input.diagnostics()[0].message(), Some("big"));
+}
+
+#[test]
+fn left_recursive_rules_keep_diagnostics_once() {
+    let digit = number::<u32>(10).validate(|n| if *n < 10 { Ok(()) } else { Err("not a digit") });
+    let sum = recursive(|sum| {
+        sum.then(just("+"))
+            .then(digit.clone())
+            .map(|((a, _), b)| a + b)
+            .or(digit.clone())
+            .memoized()
+    });
+
+    let mut input = Input::from("5+12+3");
+    assert_eq!(sum.parse(&mut input), Ok(20));
+    let positions: Vec<_> = input.diagnostics().iter().map(|e| e.pos()).collect();
+    assert_eq!(positions, [2]);
+}